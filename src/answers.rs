@@ -0,0 +1,158 @@
+use crate::{
+    error::{end_of, parse_number, Error, ParseError},
+    solution::Answer,
+};
+use std::{collections::BTreeMap, fs, io, path::PathBuf};
+
+/// The answers file used when `--answers` isn't given, checked in alongside the bundled inputs.
+pub fn default_answers_path() -> PathBuf {
+    PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/answers.txt"))
+}
+
+/// Confirmed answers, keyed by day, part and a hash of the input they were computed from, so
+/// that one file can hold the answers for several people's inputs.
+pub struct Answers {
+    path: PathBuf,
+    entries: BTreeMap<(u8, u8, u64), Answer>,
+}
+
+/// The outcome of checking an answer against the recorded one.
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: Answer },
+    Missing,
+}
+
+impl Answers {
+    /// Loads the answers file at `path`. A file that doesn't exist yet holds no answers.
+    pub fn load(path: PathBuf) -> Result<Answers, Error> {
+        let name = path.display().to_string();
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(source) => return Err(Error::Io { name, source }),
+        };
+
+        let entries = parse_entries(&contents).map_err(|source| Error::Parse { name, source })?;
+        Ok(Answers { path, entries })
+    }
+
+    pub fn check(&self, day: u8, part: u8, input: &str, answer: Answer) -> Verdict {
+        match self.entries.get(&(day, part, input_hash(input))) {
+            Some(expected) if *expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: *expected,
+            },
+            None => Verdict::Missing,
+        }
+    }
+
+    pub fn record(&mut self, day: u8, part: u8, input: &str, answer: Answer) {
+        self.entries.insert((day, part, input_hash(input)), answer);
+    }
+
+    pub fn save(&self) -> Result<(), Error> {
+        let mut contents = String::from("# day part input answer\n");
+        for ((day, part, hash), answer) in &self.entries {
+            contents += &format!("{} {} {:016x} {}\n", day, part, hash, answer);
+        }
+
+        fs::write(&self.path, contents).map_err(|source| Error::Io {
+            name: self.path.display().to_string(),
+            source,
+        })
+    }
+}
+
+fn parse_entries(contents: &str) -> Result<BTreeMap<(u8, u8, u64), Answer>, ParseError> {
+    let mut entries = BTreeMap::new();
+    for line in contents.lines() {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split_ascii_whitespace().collect();
+        let [day, part, hash, answer] = fields[..] else {
+            return Err(ParseError::at(
+                contents,
+                fields.get(4).copied().unwrap_or(end_of(line)),
+                "day, part, input hash and answer",
+            ));
+        };
+
+        let hash = u64::from_str_radix(hash, 16)
+            .map_err(|_| ParseError::at(contents, hash, "a hexadecimal input hash"))?;
+        entries.insert(
+            (
+                parse_number(contents, day)?,
+                parse_number(contents, part)?,
+                hash,
+            ),
+            parse_number(contents, answer)?,
+        );
+    }
+
+    Ok(entries)
+}
+
+/// 64-bit FNV-1a hash of the input, stable across platforms and compiler versions.
+pub fn input_hash(input: &str) -> u64 {
+    input.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn parse_entries_skips_comments_and_blank_lines() {
+        let entries =
+            parse_entries("# day part input answer\n\n1 2 00000000000000ff 31\n").unwrap();
+        assert_eq!(entries, BTreeMap::from([((1, 2, 0xff), 31)]));
+
+        let error = |contents| parse_entries(contents).unwrap_err().to_string();
+        assert_eq!(
+            error("1 2 ff\n"),
+            "1:7: expected day, part, input hash and answer, found end of line"
+        );
+        assert_eq!(
+            error("1 2 ff 31 7\n"),
+            "1:11: expected day, part, input hash and answer, found `7`"
+        );
+        assert_eq!(
+            error("1 2 fg 31\n"),
+            "1:5: expected a hexadecimal input hash, found `fg`"
+        );
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let path = env::temp_dir().join(format!("aoc-answers-{}.txt", std::process::id()));
+        let mut answers = Answers::load(path.clone()).unwrap();
+        assert_eq!(answers.check(1, 1, "input", 11), Verdict::Missing);
+
+        answers.record(1, 1, "input", 11);
+        answers.record(1, 2, "input", 31);
+        answers.save().unwrap();
+
+        let answers = Answers::load(path.clone()).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(answers.check(1, 1, "input", 11), Verdict::Pass);
+        assert_eq!(
+            answers.check(1, 2, "input", 30),
+            Verdict::Fail { expected: 31 }
+        );
+        assert_eq!(answers.check(1, 2, "other input", 31), Verdict::Missing);
+    }
+
+    #[test]
+    fn input_hash_is_fnv1a() {
+        assert_eq!(input_hash(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(input_hash("a"), 0xaf63_dc4c_8601_ec8c);
+    }
+}
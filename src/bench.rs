@@ -0,0 +1,131 @@
+use crate::{
+    error::ParseError,
+    solution::{Answer, Puzzle},
+};
+use std::time::{Duration, Instant};
+
+/// Summary of repeated timings of the same piece of work.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        assert!(!samples.is_empty());
+        samples.sort_unstable();
+
+        let mid = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+
+        Stats {
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// How long it took to parse a day's input and to solve one part of the puzzle from it.
+pub struct Timing {
+    pub day: u8,
+    pub part: u8,
+    pub parse: Stats,
+    pub solve: Stats,
+    pub answer: Option<Answer>,
+}
+
+/// Parses `input` and solves `puzzle` from it `runs` times, timing the two steps separately.
+pub fn bench(puzzle: &Puzzle, input: &str, runs: usize) -> Result<Timing, ParseError> {
+    let mut parse_samples = Vec::with_capacity(runs);
+    let mut solve_samples = Vec::with_capacity(runs);
+    let mut answer = None;
+
+    for _ in 0..runs {
+        let start = Instant::now();
+        let parsed = puzzle.parse(input)?;
+        parse_samples.push(start.elapsed());
+
+        let start = Instant::now();
+        answer = puzzle.solve_parsed(&parsed);
+        solve_samples.push(start.elapsed());
+    }
+
+    Ok(Timing {
+        day: puzzle.day,
+        part: puzzle.part,
+        parse: Stats::from_samples(parse_samples),
+        solve: Stats::from_samples(solve_samples),
+        answer,
+    })
+}
+
+/// Formats a duration in the largest unit that keeps its value at or above one.
+pub fn format_duration(d: Duration) -> String {
+    let nanos = d.as_nanos();
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.1}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.1}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2}s", d.as_secs_f64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day1::Day1;
+
+    #[test]
+    fn stats_take_the_middle_sample() {
+        let ms = Duration::from_millis;
+        assert_eq!(
+            Stats::from_samples(vec![ms(5), ms(1), ms(3)]),
+            Stats {
+                min: ms(1),
+                median: ms(3),
+                max: ms(5)
+            }
+        );
+        assert_eq!(
+            Stats::from_samples(vec![ms(8), ms(1), ms(2), ms(4)]).median,
+            ms(3)
+        );
+        assert_eq!(Stats::from_samples(vec![ms(7)]).median, ms(7));
+    }
+
+    #[test]
+    fn durations_use_the_largest_unit_above_one() {
+        let formatted = [
+            0,
+            999,
+            1_000,
+            999_949,
+            1_000_000,
+            999_949_999,
+            1_000_000_000,
+        ]
+        .map(|nanos| format_duration(Duration::from_nanos(nanos)));
+        assert_eq!(
+            formatted,
+            ["0ns", "999ns", "1.0µs", "999.9µs", "1.0ms", "999.9ms", "1.00s"]
+        );
+    }
+
+    #[test]
+    fn bench_times_every_run() {
+        let timing = bench(&Puzzle::part1::<Day1>(1), "3 4\n1 3\n", 3).unwrap();
+        assert_eq!((timing.day, timing.part, timing.answer), (1, 1, Some(3)));
+        assert!(timing.parse.min <= timing.parse.median && timing.parse.median <= timing.parse.max);
+        assert!(bench(&Puzzle::part1::<Day1>(1), "3\n", 3).is_err());
+    }
+}
@@ -0,0 +1,381 @@
+use advent_of_code_2024::{output::Format, PUZZLES};
+use std::thread;
+
+pub const USAGE: &str = "\
+usage: aoc <command> [options]
+
+commands:
+  run        solve the selected days and parts
+  bench      time parsing and solving the selected days and parts
+  verify     check the selected days and parts against recorded answers
+  reconcile  compare day 1's left and right lists of location IDs
+  list       list the available days and parts
+
+options for run, bench and verify:
+  --all             select every day
+  --day N           select a single day (may be repeated)
+  --days RANGES     select days from a list of ranges, e.g. 1-5,8,10-12
+  --part P          only solve part P (1 or 2) of each selected day
+  --input PATH      read the input for a single selected day from PATH, or stdin if PATH is -
+  --format FORMAT   print results as text, json or csv (default text)
+
+options for run and verify:
+  --jobs N          solve up to N parts at once, or one per CPU if N is 0 (default 1)
+
+options for run:
+  --render          draw the working behind each answer, for the days that support it
+  --color           color the drawings with ANSI escape codes
+  --image DIR       save images of the working behind each answer to DIR, as dayN-partP.png
+  --explain         list how each answer was reached, for the days that support it
+
+options for bench:
+  --runs N          parse and solve each part N times (default 10)
+
+options for verify:
+  --answers PATH    read and record answers in PATH (default answers.txt in the crate root)
+  --record          record the answers computed for missing parts, leaving failing ones as they are
+
+options for reconcile:
+  --input PATH      read the lists from PATH, or stdin if PATH is -
+  --top N           show the N largest distances between paired IDs (default 10)
+
+Inputs are otherwise read from dayN.txt files in $AOC_INPUT_DIR if it is set, then in
+$XDG_DATA_HOME/advent-of-code-2024 (default ~/.local/share/advent-of-code-2024) if it exists,
+and finally from the inputs bundled with the crate.";
+
+pub enum Command {
+    Run(RunArgs),
+    Bench(RunArgs),
+    Verify(RunArgs),
+    Reconcile(ReconcileArgs),
+    List,
+    Help,
+}
+
+/// The days and parts chosen on the command line.
+#[derive(Debug, Default, PartialEq)]
+pub struct Selection {
+    /// Selected days, or `None` when every day is selected.
+    pub days: Option<Vec<u8>>,
+    pub part: Option<u8>,
+}
+
+pub struct RunArgs {
+    pub selection: Selection,
+    /// Path given with `--input`, or `-` for stdin.
+    pub input: Option<String>,
+    /// How many parts `run` and `verify` solve at once.
+    pub jobs: usize,
+    /// How many times `bench` repeats each part.
+    pub runs: usize,
+    /// Answers file given to `verify` with `--answers`.
+    pub answers: Option<String>,
+    /// Whether `verify` records the answers it computes for missing parts.
+    pub record: bool,
+    pub format: Format,
+    /// Whether `run` draws the working behind each answer.
+    pub render: bool,
+    /// Whether drawings are colored.
+    pub color: bool,
+    /// Directory that `run` saves images to.
+    pub image: Option<String>,
+    /// Whether `run` explains each answer.
+    pub explain: bool,
+}
+
+pub struct ReconcileArgs {
+    /// Path given with `--input`, or `-` for stdin.
+    pub input: Option<String>,
+    /// How many of the largest distances to show.
+    pub top: usize,
+}
+
+impl Selection {
+    pub fn includes(&self, day: u8, part: u8) -> bool {
+        self.days.as_ref().is_none_or(|days| days.contains(&day))
+            && self.part.is_none_or(|p| p == part)
+    }
+}
+
+pub fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    match args.next().as_deref() {
+        None | Some("help" | "-h" | "--help") => Ok(Command::Help),
+        Some("list") => match args.next() {
+            None => Ok(Command::List),
+            Some(arg) => Err(format!("unexpected argument '{}' for list", arg)),
+        },
+        Some("run") => parse_run_args("run", args).map(Command::Run),
+        Some("bench") => parse_run_args("bench", args).map(Command::Bench),
+        Some("verify") => parse_run_args("verify", args).map(Command::Verify),
+        Some("reconcile") => parse_reconcile_args(args).map(Command::Reconcile),
+        Some(command) => Err(format!("unknown command '{}'", command)),
+    }
+}
+
+fn parse_run_args(
+    command: &str,
+    mut args: impl Iterator<Item = String>,
+) -> Result<RunArgs, String> {
+    let mut all = false;
+    let mut days: Vec<u8> = Vec::new();
+    let mut part = None;
+    let mut input = None;
+    let mut jobs = 1;
+    let mut runs = 10;
+    let mut answers = None;
+    let mut record = false;
+    let mut format = Format::Text;
+    let mut render = false;
+    let mut color = false;
+    let mut image = None;
+    let mut explain = false;
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("missing value for {}", arg))
+        };
+
+        match arg.as_str() {
+            "--all" => all = true,
+            "--day" => days.push(parse_day(&value()?)?),
+            "--days" => days.extend(parse_day_ranges(&value()?)?),
+            "--part" => {
+                part = match value()?.as_str() {
+                    "1" => Some(1),
+                    "2" => Some(2),
+                    p => return Err(format!("invalid part '{}', expected 1 or 2", p)),
+                }
+            }
+            "--input" => input = Some(value()?),
+            "--format" => format = value()?.parse()?,
+            "--jobs" if command != "bench" => {
+                let v = value()?;
+                jobs = match v.parse::<usize>() {
+                    Ok(0) => thread::available_parallelism().map_or(1, |n| n.get()),
+                    Ok(n) => n,
+                    _ => return Err(format!("invalid number of jobs '{}'", v)),
+                }
+            }
+            "--runs" if command == "bench" => {
+                let v = value()?;
+                runs = match v.parse::<usize>() {
+                    Ok(n) if n > 0 => n,
+                    _ => return Err(format!("invalid number of runs '{}'", v)),
+                }
+            }
+            "--render" if command == "run" => render = true,
+            "--color" if command == "run" => color = true,
+            "--image" if command == "run" => image = Some(value()?),
+            "--explain" if command == "run" => explain = true,
+            "--answers" if command == "verify" => answers = Some(value()?),
+            "--record" if command == "verify" => record = true,
+            _ => return Err(format!("unexpected argument '{}' for {}", arg, command)),
+        }
+    }
+
+    if all && !days.is_empty() {
+        return Err("--all cannot be combined with --day or --days".to_string());
+    }
+
+    if !all && days.is_empty() {
+        return Err(format!(
+            "select days to {} with --day, --days or --all",
+            command
+        ));
+    }
+
+    days.sort_unstable();
+    days.dedup();
+
+    for day in &days {
+        if !PUZZLES
+            .iter()
+            .any(|p| p.day == *day && part.is_none_or(|part| p.part == part))
+        {
+            return Err(match part {
+                Some(part) if PUZZLES.iter().any(|p| p.day == *day) => {
+                    format!("no solution for day {} part {}, see `aoc list`", day, part)
+                }
+                _ => format!("no solution for day {}, see `aoc list`", day),
+            });
+        }
+    }
+
+    if input.is_some() && (all || days.len() > 1) {
+        return Err("--input can only be used when a single day is selected".to_string());
+    }
+
+    if render && format != Format::Text {
+        return Err("--render can only be used with text output".to_string());
+    }
+
+    if explain && format != Format::Text {
+        return Err("--explain can only be used with text output".to_string());
+    }
+
+    if color && !render {
+        return Err("--color can only be used with --render".to_string());
+    }
+
+    Ok(RunArgs {
+        selection: Selection {
+            days: if all { None } else { Some(days) },
+            part,
+        },
+        input,
+        jobs,
+        runs,
+        answers,
+        record,
+        format,
+        render,
+        color,
+        image,
+        explain,
+    })
+}
+
+fn parse_reconcile_args(mut args: impl Iterator<Item = String>) -> Result<ReconcileArgs, String> {
+    let mut input = None;
+    let mut top = 10;
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("missing value for {}", arg))
+        };
+
+        match arg.as_str() {
+            "--input" => input = Some(value()?),
+            "--top" => {
+                let v = value()?;
+                top = v
+                    .parse()
+                    .map_err(|_| format!("invalid number of distances '{}'", v))?;
+            }
+            _ => return Err(format!("unexpected argument '{}' for reconcile", arg)),
+        }
+    }
+
+    Ok(ReconcileArgs { input, top })
+}
+
+fn parse_day(s: &str) -> Result<u8, String> {
+    match s.trim().parse::<u8>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!(
+            "invalid day '{}', expected a number from 1 to 25",
+            s
+        )),
+    }
+}
+
+fn parse_day_ranges(s: &str) -> Result<Vec<u8>, String> {
+    let mut days = Vec::new();
+    for range in s.split(',') {
+        match range.split_once('-') {
+            Some((first, last)) => {
+                let (first, last) = (parse_day(first)?, parse_day(last)?);
+                if first > last {
+                    return Err(format!(
+                        "invalid range '{}', {} is after {}",
+                        range, first, last
+                    ));
+                }
+                days.extend(first..=last);
+            }
+            None => days.push(parse_day(range)?),
+        }
+    }
+
+    Ok(days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_args(args: &str) -> Result<RunArgs, String> {
+        parse_run_args("run", args.split_whitespace().map(String::from))
+    }
+
+    fn error(args: &str) -> String {
+        run_args(args).err().unwrap()
+    }
+
+    #[test]
+    fn day_ranges() {
+        assert_eq!(parse_day_ranges("1-3,5,7-8"), Ok(vec![1, 2, 3, 5, 7, 8]));
+        assert_eq!(parse_day_ranges(" 4 "), Ok(vec![4]));
+        assert_eq!(
+            parse_day_ranges("5-3"),
+            Err("invalid range '5-3', 5 is after 3".to_string())
+        );
+        assert_eq!(
+            parse_day_ranges("1,26"),
+            Err("invalid day '26', expected a number from 1 to 25".to_string())
+        );
+        assert!(parse_day_ranges("1-").is_err());
+    }
+
+    #[test]
+    fn selection_merges_days_and_parts() {
+        let args = run_args("--days 1-3 --day 2 --part 2").unwrap();
+        assert_eq!(
+            args.selection,
+            Selection {
+                days: Some(vec![1, 2, 3]),
+                part: Some(2)
+            }
+        );
+        assert!(args.selection.includes(3, 2));
+        assert!(!args.selection.includes(3, 1));
+        assert!(!args.selection.includes(4, 2));
+
+        let args = run_args("--all").unwrap();
+        assert_eq!(args.selection, Selection::default());
+        assert!(args.selection.includes(12, 1));
+    }
+
+    #[test]
+    fn conflicting_options_are_rejected() {
+        assert_eq!(error(""), "select days to run with --day, --days or --all");
+        assert_eq!(
+            error("--all --day 1"),
+            "--all cannot be combined with --day or --days"
+        );
+        assert_eq!(
+            error("--days 1-2 --input day1.txt"),
+            "--input can only be used when a single day is selected"
+        );
+        assert_eq!(
+            error("--day 1 --color"),
+            "--color can only be used with --render"
+        );
+        assert_eq!(
+            error("--day 1 --render --format json"),
+            "--render can only be used with text output"
+        );
+        assert_eq!(
+            error("--day 1 --runs 3"),
+            "unexpected argument '--runs' for run"
+        );
+        assert_eq!(
+            error("--day 1 --part 3"),
+            "invalid part '3', expected 1 or 2"
+        );
+        assert_eq!(error("--day"), "missing value for --day");
+    }
+
+    #[test]
+    fn selections_without_a_solution_are_rejected() {
+        assert_eq!(error("--day 13"), "no solution for day 13, see `aoc list`");
+        assert_eq!(
+            error("--day 12 --part 2"),
+            "no solution for day 12 part 2, see `aoc list`"
+        );
+        assert!(run_args("--day 12 --part 1").is_ok());
+        assert!(run_args("--all --part 2").is_ok());
+    }
+}
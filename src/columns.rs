@@ -0,0 +1,161 @@
+use crate::error::{end_of, parse_number, ParseError};
+use itertools::Either;
+use std::str::FromStr;
+
+/// What separates the columns on each line.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Delimiter {
+    /// Any run of spaces and tabs.
+    Whitespace,
+    /// A single character such as `,` or `\t`, optionally surrounded by spaces.
+    Char(char),
+}
+
+impl Delimiter {
+    fn split(self, line: &str) -> impl Iterator<Item = &str> {
+        match self {
+            Delimiter::Whitespace => Either::Left(line.split_ascii_whitespace()),
+            Delimiter::Char(c) => Either::Right(line.split(c).map(str::trim)),
+        }
+    }
+}
+
+/// How the columns of a table are laid out.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Layout {
+    pub delimiter: Delimiter,
+    /// Whether the first line names the columns rather than holding values.
+    pub header: bool,
+}
+
+impl Layout {
+    /// Guesses the layout from the first line of `input`: comma-separated if it contains a comma,
+    /// tab-separated if it contains a tab but no spaces, and separated by whitespace otherwise.
+    /// It's taken to be a header if it doesn't start with a digit.
+    pub fn detect(input: &str) -> Layout {
+        let first = input.lines().next().unwrap_or("");
+        let delimiter = if first.contains(',') {
+            Delimiter::Char(',')
+        } else if first.contains('\t') && !first.contains(' ') {
+            Delimiter::Char('\t')
+        } else {
+            Delimiter::Whitespace
+        };
+
+        Layout {
+            delimiter,
+            header: first
+                .trim_start()
+                .starts_with(|c: char| !c.is_ascii_digit()),
+        }
+    }
+}
+
+/// Parses `input` as a table of numbers with exactly `N` columns, returning the values of each
+/// column in order.
+pub fn parse_columns<T: FromStr, const N: usize>(
+    input: &str,
+    layout: Layout,
+) -> Result<[Vec<T>; N], ParseError> {
+    let mut columns: [Vec<T>; N] = std::array::from_fn(|_| Vec::new());
+    for line in input.lines().skip(layout.header as usize) {
+        let mut fields = layout.delimiter.split(line);
+        for column in &mut columns {
+            let field = fields
+                .next()
+                .ok_or_else(|| ParseError::at(input, end_of(line), format!("{} columns", N)))?;
+            column.push(parse_number(input, field)?);
+        }
+
+        if let Some(extra) = fields.next() {
+            return Err(ParseError::at(
+                input,
+                extra_field(line, extra),
+                "end of line",
+            ));
+        }
+    }
+
+    Ok(columns)
+}
+
+/// Returns the token to blame for an extra `field` on `line`: the field itself, or the delimiter
+/// before it if the field is empty.
+fn extra_field<'a>(line: &'a str, field: &'a str) -> &'a str {
+    if !field.is_empty() {
+        return field;
+    }
+
+    let offset = field.as_ptr() as usize - line.as_ptr() as usize;
+    let before = line[..offset].trim_end();
+    let delimiter = before.chars().next_back().map_or(0, char::len_utf8);
+    &before[before.len() - delimiter..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_layouts() {
+        assert_eq!(
+            Layout::detect("3   4\n"),
+            Layout {
+                delimiter: Delimiter::Whitespace,
+                header: false
+            }
+        );
+        assert_eq!(
+            Layout::detect("left, right\n3, 4\n"),
+            Layout {
+                delimiter: Delimiter::Char(','),
+                header: true
+            }
+        );
+        assert_eq!(Layout::detect("3\t4\n").delimiter, Delimiter::Char('\t'));
+    }
+
+    #[test]
+    fn parse_any_number_of_columns() {
+        let input = "a,b,c\n1, 2 ,3\n4,5,6\n";
+        let columns: [Vec<u8>; 3] = parse_columns(input, Layout::detect(input)).unwrap();
+        assert_eq!(columns, [vec![1, 4], vec![2, 5], vec![3, 6]]);
+
+        let input = "1\t2\n3\t4\n";
+        let columns: [Vec<u8>; 2] = parse_columns(input, Layout::detect(input)).unwrap();
+        assert_eq!(columns, [vec![1, 3], vec![2, 4]]);
+    }
+
+    #[test]
+    fn errors_name_the_line() {
+        let whitespace = Layout {
+            delimiter: Delimiter::Whitespace,
+            header: false,
+        };
+        let error = |input| {
+            parse_columns::<u8, 2>(input, whitespace)
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            error("1 2\n3\n"),
+            "2:2: expected 2 columns, found end of line"
+        );
+        assert_eq!(
+            error("1 2\n3 4 5\n"),
+            "2:5: expected end of line, found `5`"
+        );
+        assert_eq!(error("1 2\n3 x\n"), "2:3: expected a number, found `x`");
+
+        let csv = Layout {
+            delimiter: Delimiter::Char(','),
+            header: false,
+        };
+        assert_eq!(
+            parse_columns::<u8, 2>("1,2\n3,4,\n", csv)
+                .unwrap_err()
+                .to_string(),
+            "2:4: expected end of line, found `,`"
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{
+    columns::{parse_columns, Layout},
+    error::ParseError,
+    solution::{Answer, Solution},
+};
+use itertools::Itertools;
+use std::{cmp::Reverse, collections::BTreeMap, fmt};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = (Vec<u64>, Vec<u64>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let [left, right] = parse_columns(input, Layout::detect(input))?;
+        Ok((left, right))
+    }
+
+    fn part1((left, right): &Self::Input) -> Answer {
+        calculate_left_right_list_distance(left, right)
+    }
+
+    fn part2((left, right): &Self::Input) -> Option<Answer> {
+        Some(calculate_left_right_list_similarity_score(left, right))
+    }
+}
+
+pub fn calculate_left_right_list_distance(left: &[u64], right: &[u64]) -> u64 {
+    let mut left = left.to_vec();
+    let mut right = right.to_vec();
+    left.sort_unstable();
+    right.sort_unstable();
+    left.into_iter()
+        .zip(right)
+        .map(|(l, r)| r.abs_diff(l))
+        .sum::<u64>()
+}
+
+pub fn calculate_left_right_list_similarity_score(left: &[u64], right: &[u64]) -> u64 {
+    let right_id_counts = right.iter().counts();
+    left.iter()
+        .map(|id| id * *right_id_counts.get(id).unwrap_or(&0) as u64)
+        .sum::<u64>()
+}
+
+/// How two lists of location IDs differ.
+#[derive(Debug, PartialEq)]
+pub struct Reconciliation {
+    /// IDs that appear in the left list but not the right, in order.
+    pub only_left: Vec<u64>,
+    /// IDs that appear in the right list but not the left, in order.
+    pub only_right: Vec<u64>,
+    /// Every ID with the number of times it appears in the left and right lists, in order.
+    pub counts: Vec<(u64, usize, usize)>,
+    /// The pairs of IDs that are furthest apart once both lists are sorted, as `(left, right,
+    /// distance)`, furthest first.
+    pub largest_distances: Vec<(u64, u64, u64)>,
+}
+
+/// Compares the two lists, keeping the `top` largest pairwise distances.
+pub fn reconcile_left_right_lists(left: &[u64], right: &[u64], top: usize) -> Reconciliation {
+    let mut counts: BTreeMap<u64, (usize, usize)> = BTreeMap::new();
+    for id in left {
+        counts.entry(*id).or_default().0 += 1;
+    }
+    for id in right {
+        counts.entry(*id).or_default().1 += 1;
+    }
+
+    let mut left = left.to_vec();
+    let mut right = right.to_vec();
+    left.sort_unstable();
+    right.sort_unstable();
+    let mut distances = left
+        .into_iter()
+        .zip(right)
+        .map(|(l, r)| (l, r, r.abs_diff(l)))
+        .collect_vec();
+    distances.sort_by_key(|(_, _, distance)| Reverse(*distance));
+    distances.truncate(top);
+
+    Reconciliation {
+        only_left: counts
+            .iter()
+            .filter(|(_, (_, r))| *r == 0)
+            .map(|(id, _)| *id)
+            .collect(),
+        only_right: counts
+            .iter()
+            .filter(|(_, (l, _))| *l == 0)
+            .map(|(id, _)| *id)
+            .collect(),
+        counts: counts.into_iter().map(|(id, (l, r))| (id, l, r)).collect(),
+        largest_distances: distances,
+    }
+}
+
+impl fmt::Display for Reconciliation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "only in left ({}): {}",
+            self.only_left.len(),
+            self.only_left.iter().join(", ")
+        )?;
+        writeln!(
+            f,
+            "only in right ({}): {}",
+            self.only_right.len(),
+            self.only_right.iter().join(", ")
+        )?;
+
+        writeln!(f, "\nlargest distances:")?;
+        writeln!(f, "{:>12} {:>12} {:>12}", "left", "right", "distance")?;
+        for (left, right, distance) in &self.largest_distances {
+            writeln!(f, "{:>12} {:>12} {:>12}", left, right, distance)?;
+        }
+
+        writeln!(f, "\ncounts:")?;
+        writeln!(f, "{:>12} {:>12} {:>12}", "id", "left", "right")?;
+        for (id, left, right) in &self.counts {
+            writeln!(f, "{:>12} {:>12} {:>12}", id, left, right)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(Day1::solve(EXAMPLE).unwrap(), (11, Some(31)));
+    }
+
+    #[test]
+    fn parse_csv_with_header() {
+        let input = "left,right\n3,4\n4,3\n";
+        assert_eq!(Day1::parse(input).unwrap(), (vec![3, 4], vec![4, 3]));
+    }
+
+    #[test]
+    fn reconcile_example() {
+        let (left, right) = Day1::parse(EXAMPLE).unwrap();
+        let report = reconcile_left_right_lists(&left, &right, 2);
+        assert_eq!(report.only_left, [1, 2]);
+        assert_eq!(report.only_right, [5, 9]);
+        assert_eq!(
+            report.counts,
+            [
+                (1, 1, 0),
+                (2, 1, 0),
+                (3, 3, 3),
+                (4, 1, 1),
+                (5, 0, 1),
+                (9, 0, 1)
+            ]
+        );
+        assert_eq!(report.largest_distances, [(4, 9, 5), (1, 3, 2)]);
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{
+    error::ParseError,
+    grid::{Grid, Position},
+    image::{heat, shade, Image},
+    render::{Color, Render},
+    search::{count_paths, reachable},
+    solution::{Answer, Solution},
+};
+use std::collections::HashSet;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Grid<u8>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::parse(input, |c| c.to_digit(10).map(|d| d as u8), "a digit")
+    }
+
+    fn part1(topo_map: &Self::Input) -> Answer {
+        sum_scores_of_all_trailheads_on_topo_map(topo_map) as Answer
+    }
+
+    fn part2(topo_map: &Self::Input) -> Option<Answer> {
+        Some(sum_ratings_of_all_trailheads_on_topo_map(topo_map) as Answer)
+    }
+
+    fn render(topo_map: &Self::Input, part: u8) -> Option<Render> {
+        // both parts follow the same trails; positions off every trail are drawn as impassable,
+        // and part two counts the trails through each position instead of showing its elevation
+        let trail = find_trail_positions(topo_map);
+        let off_trail = topo_map.positions().filter(|p| !trail.contains(p));
+        let render = Render::new(topo_map, |h| char::from_digit(*h as u32, 10).unwrap()).highlight(
+            off_trail,
+            Some('.'),
+            Color::Blue,
+        );
+        Some(match part {
+            1 => render.highlight(trail, None, Color::Green),
+            _ => {
+                let counts = count_trails_through_positions(topo_map);
+                render.counts(trail.into_iter().map(|p| (p, counts[p])), Color::Green)
+            }
+        })
+    }
+
+    fn image(topo_map: &Self::Input, part: u8) -> Option<Image> {
+        if part == 2 {
+            // hotter the more trails pass through
+            let counts = count_trails_through_positions(topo_map);
+            let max = counts.iter().map(|(_, count)| *count).max().unwrap_or(0);
+            return Some(Image::from_grid(&counts, |_, count| heat(*count, max)));
+        }
+
+        // shaded by elevation, with the trails in green
+        let trail = find_trail_positions(topo_map);
+        Some(Image::from_grid(topo_map, |p, h| {
+            let color = if trail.contains(&p) {
+                [90, 255, 90]
+            } else {
+                [200, 200, 200]
+            };
+            shade(color, *h as usize, 9)
+        }))
+    }
+}
+
+/// The positions one step up from `position`, i.e. its neighbors that are one higher.
+fn climb(topo_map: &Grid<u8>, position: Position) -> impl Iterator<Item = Position> + '_ {
+    let elevation = topo_map[position];
+    topo_map
+        .neighbors4(position)
+        .filter(move |&p| topo_map[p] == elevation + 1)
+}
+
+/// The number of peaks that can be reached from `trailhead`.
+pub fn trailhead_score(topo_map: &Grid<u8>, trailhead: Position) -> usize {
+    reachable([trailhead], |p| climb(topo_map, p))
+        .into_iter()
+        .filter(|&p| topo_map[p] == 9)
+        .count()
+}
+
+/// The number of distinct hiking trails that start at `trailhead`.
+pub fn trailhead_rating(topo_map: &Grid<u8>, trailhead: Position) -> usize {
+    count_paths(trailhead, |p| topo_map[p] == 9, |p| climb(topo_map, p))
+}
+
+pub fn sum_scores_of_all_trailheads_on_topo_map(topo_map: &Grid<u8>) -> usize {
+    topo_map
+        .positions_of(&0)
+        .map(|trailhead| trailhead_score(topo_map, trailhead))
+        .sum()
+}
+
+pub fn sum_ratings_of_all_trailheads_on_topo_map(topo_map: &Grid<u8>) -> usize {
+    topo_map
+        .positions_of(&0)
+        .map(|trailhead| trailhead_rating(topo_map, trailhead))
+        .sum()
+}
+
+/// Returns every position on a hiking trail, i.e. a path from a trailhead at elevation 0 to a peak
+/// at elevation 9 that climbs by one at each step.
+pub fn find_trail_positions(topo_map: &Grid<u8>) -> HashSet<Position> {
+    // climb from every trailhead, then descend from the peaks that were reached; the trails are
+    // the positions visited both ways
+    let climbed = reachable(topo_map.positions_of(&0), |p| climb(topo_map, p));
+    let peaks = climbed.iter().copied().filter(|&p| topo_map[p] == 9);
+    let descend = |position: Position| {
+        topo_map
+            .neighbors4(position)
+            .filter(move |&p| topo_map[p] + 1 == topo_map[position])
+    };
+
+    reachable(peaks, descend)
+        .into_iter()
+        .filter(|p| climbed.contains(p))
+        .collect()
+}
+
+/// Counts the distinct hiking trails through each position, which is the number of ways to climb
+/// to it from a trailhead times the number of ways to climb from it to a peak.
+pub fn count_trails_through_positions(topo_map: &Grid<u8>) -> Grid<usize> {
+    let mut by_elevation = topo_map.positions().collect::<Vec<_>>();
+    by_elevation.sort_by_key(|&p| topo_map[p]);
+
+    let mut up = topo_map.map(|h| (*h == 0) as usize);
+    for &position in &by_elevation {
+        for next in climb(topo_map, position) {
+            up[next] += up[position];
+        }
+    }
+
+    let mut down = topo_map.map(|h| (*h == 9) as usize);
+    for &position in by_elevation.iter().rev() {
+        if topo_map[position] != 9 {
+            down[position] = climb(topo_map, position).map(|next| down[next]).sum();
+        }
+    }
+
+    let mut counts = up;
+    for position in topo_map.positions() {
+        counts[position] *= down[position];
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(Day10::solve(EXAMPLE).unwrap(), (36, Some(81)));
+    }
+
+    #[test]
+    fn trailhead_counts_reachable_peaks_and_distinct_trails() {
+        let topo_map = Day10::parse(EXAMPLE).unwrap();
+        assert_eq!(trailhead_score(&topo_map, (0, 2)), 5);
+        assert_eq!(trailhead_rating(&topo_map, (0, 2)), 20);
+    }
+
+    #[test]
+    fn count_trails_through_each_position() {
+        let topo_map = Day10::parse(EXAMPLE).unwrap();
+        let counts = count_trails_through_positions(&topo_map);
+        assert_eq!(counts[(0, 2)], 20);
+        assert_eq!(
+            topo_map.positions_of(&0).map(|p| counts[p]).sum::<usize>(),
+            81
+        );
+        assert_eq!(
+            counts
+                .iter()
+                .filter(|(_, count)| **count > 0)
+                .map(|(p, _)| p)
+                .collect::<HashSet<_>>(),
+            find_trail_positions(&topo_map)
+        );
+        assert!(Day10::render(&topo_map, 2).is_some());
+        assert!(Day10::image(&topo_map, 2).is_some());
+    }
+}
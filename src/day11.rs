@@ -0,0 +1,114 @@
+use crate::{
+    error::{parse_number, ParseError},
+    solution::{Answer, Solution},
+};
+use std::collections::HashMap;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<u64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .split_ascii_whitespace()
+            .map(|n| parse_number(input, n))
+            .collect()
+    }
+
+    fn part1(stones: &Self::Input) -> Answer {
+        sum_stones_after_25_blinks(stones) as Answer
+    }
+
+    fn part2(stones: &Self::Input) -> Option<Answer> {
+        Some(sum_stones_after_75_blinks(stones) as Answer)
+    }
+}
+
+pub fn sum_stones_after_n_blinks(initial_stones: &[u64], blinks: usize) -> i64 {
+    let mut stones: HashMap<u64, i64> = HashMap::new();
+    for stone in initial_stones {
+        stones
+            .entry(*stone)
+            .and_modify(|count| *count += 1)
+            .or_insert(1);
+    }
+
+    for _ in 0..blinks {
+        let mut new_stones: HashMap<u64, i64> = HashMap::new();
+        for (key, value) in &stones {
+            match key {
+                0 => {
+                    new_stones
+                        .entry(1)
+                        .and_modify(|count| *count += value)
+                        .or_insert(*value);
+                    new_stones
+                        .entry(0)
+                        .and_modify(|count| *count -= value)
+                        .or_insert(-value);
+                }
+                n => {
+                    let s = n.to_string();
+                    let len = s.len();
+                    if len % 2 == 0 {
+                        let nums = s.split_at(len / 2);
+                        new_stones
+                            .entry(nums.0.parse::<u64>().unwrap())
+                            .and_modify(|count| *count += value)
+                            .or_insert(*value);
+                        new_stones
+                            .entry(nums.1.parse::<u64>().unwrap())
+                            .and_modify(|count| *count += value)
+                            .or_insert(*value);
+                        new_stones
+                            .entry(*n)
+                            .and_modify(|count| *count -= value)
+                            .or_insert(-value);
+                    } else {
+                        new_stones
+                            .entry(n * 2024)
+                            .and_modify(|count| *count += value)
+                            .or_insert(*value);
+                        new_stones
+                            .entry(*n)
+                            .and_modify(|count| *count -= value)
+                            .or_insert(-value);
+                    }
+                }
+            }
+        }
+
+        for (stone, count) in new_stones {
+            stones
+                .entry(stone)
+                .and_modify(|c| *c += count)
+                .or_insert(count);
+        }
+
+        stones.retain(|_, v| *v > 0);
+    }
+
+    stones.values().sum::<i64>()
+}
+
+pub fn sum_stones_after_25_blinks(stones: &[u64]) -> i64 {
+    sum_stones_after_n_blinks(stones, 25)
+}
+
+pub fn sum_stones_after_75_blinks(stones: &[u64]) -> i64 {
+    sum_stones_after_n_blinks(stones, 75)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            Day11::solve("125 17\n").unwrap(),
+            (55312, Some(65601038650482))
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{
+    error::ParseError,
+    grid::{Grid, Position},
+    image::{palette, Image},
+    render::Render,
+    search::connected_components,
+    solution::{Answer, Solution},
+};
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::parse_chars(input)
+    }
+
+    fn part1(garden: &Self::Input) -> Answer {
+        total_fencing_price_for_all_regions(garden) as Answer
+    }
+
+    fn render(garden: &Self::Input, _part: u8) -> Option<Render> {
+        let (region_ids, _) = discover_regions(garden);
+        Some(Render::new(garden, |c| *c).regions(&region_ids))
+    }
+
+    fn image(garden: &Self::Input, _part: u8) -> Option<Image> {
+        let (region_ids, _) = discover_regions(garden);
+        Some(Image::from_grid(&region_ids, |_, id| palette(*id)))
+    }
+}
+
+/// The plots next to `position` with the same type of plant.
+fn same_plant_neighbors(
+    garden: &Grid<char>,
+    position: Position,
+) -> impl Iterator<Item = Position> + '_ {
+    garden
+        .neighbors4(position)
+        .filter(move |&p| garden[p] == garden[position])
+}
+
+/// Groups the plots of the garden into regions, numbered in the order they're discovered. Returns
+/// the region of every plot, along with the area and perimeter of each region.
+pub fn discover_regions(garden: &Grid<char>) -> (Grid<usize>, Vec<(usize, usize)>) {
+    let mut region_ids = garden.map(|_| 0);
+    let mut regions = Vec::new();
+    for (id, plots) in connected_components(garden.positions(), |p| same_plant_neighbors(garden, p))
+        .into_iter()
+        .enumerate()
+    {
+        // each plot is fenced on every side that doesn't border the same region
+        let mut perimeter = 0;
+        for &plot in &plots {
+            region_ids[plot] = id;
+            perimeter += 4 - same_plant_neighbors(garden, plot).count();
+        }
+        regions.push((plots.len(), perimeter));
+    }
+
+    (region_ids, regions)
+}
+
+pub fn total_fencing_price_for_all_regions(garden: &Grid<char>) -> usize {
+    let (_, regions) = discover_regions(garden);
+    regions
+        .into_iter()
+        .map(|(area, perimeter)| area * perimeter)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        let garden = Day12::parse("AAAA\nBBCD\nBBCC\nEEEC\n").unwrap();
+        assert_eq!(Day12::part1(&garden), 140);
+
+        let garden = Day12::parse("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO\n").unwrap();
+        assert_eq!(Day12::part1(&garden), 772);
+
+        let garden = Day12::parse(
+            "\
+RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE
+",
+        )
+        .unwrap();
+        assert_eq!(Day12::part1(&garden), 1930);
+    }
+
+    #[test]
+    fn discover_regions_measures_area_and_perimeter() {
+        let garden = Day12::parse("AAAA\nBBCD\nBBCC\nEEEC\n").unwrap();
+        let (region_ids, regions) = discover_regions(&garden);
+        assert_eq!(regions, [(4, 10), (4, 8), (4, 10), (1, 4), (3, 8)]);
+        assert!(garden.positions_of(&'C').all(|p| region_ids[p] == 2));
+    }
+}
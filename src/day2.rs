@@ -0,0 +1,370 @@
+use crate::{
+    error::{parse_number, ParseError},
+    solution::{Answer, Solution},
+};
+use itertools::Itertools;
+use std::{cmp::Ordering, fmt};
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Vec<u64>>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        get_reports(input)
+    }
+
+    fn part1(reports: &Self::Input) -> Answer {
+        sum_safe_reports(reports) as Answer
+    }
+
+    fn part2(reports: &Self::Input) -> Option<Answer> {
+        Some(sum_safe_reports_with_problem_dampener(reports) as Answer)
+    }
+
+    fn explain(reports: &Self::Input, part: u8) -> Option<String> {
+        let check = match part {
+            1 => is_report_safe,
+            _ => is_report_safe_with_problem_dampener,
+        };
+        Some(
+            reports
+                .iter()
+                .enumerate()
+                .map(|(i, levels)| {
+                    format!(
+                        "report {} ({}): {}\n",
+                        i + 1,
+                        levels.iter().join(" "),
+                        check(levels)
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+/// The rules a report's levels must follow to be safe.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SafetyPolicy {
+    /// The smallest difference allowed between adjacent levels. If it's zero, adjacent levels
+    /// may be equal, and equal levels don't count as a change in direction.
+    pub min_step: u64,
+    /// The largest difference allowed between adjacent levels.
+    pub max_step: u64,
+    /// How many bad levels the problem dampener may remove.
+    pub removals: usize,
+}
+
+impl SafetyPolicy {
+    /// The rules from part one.
+    pub const STRICT: SafetyPolicy = SafetyPolicy {
+        min_step: 1,
+        max_step: 3,
+        removals: 0,
+    };
+
+    /// The rules from part two, with the problem dampener removing one bad level.
+    pub const DAMPENED: SafetyPolicy = SafetyPolicy {
+        removals: 1,
+        ..SafetyPolicy::STRICT
+    };
+
+    /// Whether level `b` may follow level `a` in a report whose levels change in `direction`.
+    fn allows(&self, a: u64, b: u64, direction: Ordering) -> bool {
+        let step = a.abs_diff(b);
+        (step == 0 || b.cmp(&a) == direction) && (self.min_step..=self.max_step).contains(&step)
+    }
+}
+
+/// Whether a report is safe, and if not, why not.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Verdict {
+    Safe,
+    /// Safe once the problem dampener removes the levels at these indices.
+    SafeWithoutLevels(Vec<usize>),
+    Unsafe(Problem),
+}
+
+/// The first rule an unsafe report breaks. Each index is that of the level which breaks the rule
+/// by how it differs from the level before it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Problem {
+    /// The levels turn from increasing to decreasing, or the other way around.
+    DirectionChange { index: usize },
+    /// The level is the same as the one before it.
+    NoChange { index: usize },
+    /// The level differs from the one before it by less than the policy allows.
+    StepTooSmall { index: usize, step: u64 },
+    /// The level differs from the one before it by more than the policy allows.
+    StepTooLarge { index: usize, step: u64 },
+}
+
+impl Verdict {
+    pub fn is_safe(&self) -> bool {
+        !matches!(self, Verdict::Unsafe(_))
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Safe => write!(f, "safe"),
+            Verdict::SafeWithoutLevels(indices) => match &indices[..] {
+                [index] => write!(f, "safe without the level at index {}", index),
+                _ => write!(
+                    f,
+                    "safe without the levels at indices {}",
+                    indices.iter().join(", ")
+                ),
+            },
+            Verdict::Unsafe(problem) => write!(f, "unsafe, {}", problem),
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::DirectionChange { index } => write!(f, "direction changes at index {}", index),
+            Problem::NoChange { index } => write!(f, "no change at index {}", index),
+            Problem::StepTooSmall { index, step } => {
+                write!(f, "step of {} at index {} is too small", step, index)
+            }
+            Problem::StepTooLarge { index, step } => {
+                write!(f, "step of {} at index {} is too large", step, index)
+            }
+        }
+    }
+}
+
+pub fn get_reports(input: &str) -> Result<Vec<Vec<u64>>, ParseError> {
+    let lines = input.lines();
+    lines
+        .map(|l| -> Result<Vec<u64>, ParseError> {
+            l.split_ascii_whitespace()
+                .map(|level| parse_number(input, level))
+                .collect()
+        })
+        .collect()
+}
+
+pub fn is_report_safe(levels: &[u64]) -> Verdict {
+    check_report(levels, &SafetyPolicy::STRICT)
+}
+
+/// Like [`is_report_safe`], but tolerating a single bad level.
+pub fn is_report_safe_with_problem_dampener(levels: &[u64]) -> Verdict {
+    check_report(levels, &SafetyPolicy::DAMPENED)
+}
+
+/// Checks a report against `policy`. A report that is only safe once the problem dampener
+/// removes some levels names as few levels as possible; a report that stays unsafe gives the
+/// first problem with all of its levels.
+pub fn check_report(levels: &[u64], policy: &SafetyPolicy) -> Verdict {
+    match first_problem(levels, policy) {
+        None => Verdict::Safe,
+        Some(problem) => match levels_to_remove(levels, policy) {
+            Some(indices) => Verdict::SafeWithoutLevels(indices),
+            None => Verdict::Unsafe(problem),
+        },
+    }
+}
+
+pub fn count_safe_reports(reports: &[Vec<u64>], policy: &SafetyPolicy) -> usize {
+    reports
+        .iter()
+        .filter(|levels| check_report(levels, policy).is_safe())
+        .count()
+}
+
+pub fn sum_safe_reports(reports: &[Vec<u64>]) -> usize {
+    count_safe_reports(reports, &SafetyPolicy::STRICT)
+}
+
+pub fn sum_safe_reports_with_problem_dampener(reports: &[Vec<u64>]) -> usize {
+    count_safe_reports(reports, &SafetyPolicy::DAMPENED)
+}
+
+fn first_problem(levels: &[u64], policy: &SafetyPolicy) -> Option<Problem> {
+    // A report is safe if:
+    // 1. All levels are in increasing or decreasing order
+    // 2. A level differs from its predecessor by at least the minimum step and at most the
+    //    maximum step
+    let mut direction = None;
+    for (index, w) in levels.windows(2).enumerate().map(|(i, w)| (i + 1, w)) {
+        let step = w[0].abs_diff(w[1]);
+        match w[1].cmp(&w[0]) {
+            Ordering::Equal if policy.min_step > 0 => return Some(Problem::NoChange { index }),
+            Ordering::Equal => {}
+            order if *direction.get_or_insert(order) != order => {
+                return Some(Problem::DirectionChange { index })
+            }
+            _ if step < policy.min_step => return Some(Problem::StepTooSmall { index, step }),
+            _ if step > policy.max_step => return Some(Problem::StepTooLarge { index, step }),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Finds the fewest levels, at most `policy.removals`, that leave a safe report once removed, and
+/// returns their indices in order.
+///
+/// This is a single pass over the levels which works out, for each level and each direction, the
+/// fewest removals that leave a safe run of levels ending with that one. A run ending at level
+/// `j` can only continue one of the `removals + 1` runs ending just before it, since any
+/// further back would need too many removals in between, so the pass takes `O(n * removals)`
+/// time.
+fn levels_to_remove(levels: &[u64], policy: &SafetyPolicy) -> Option<Vec<usize>> {
+    const DIRECTIONS: [Ordering; 2] = [Ordering::Greater, Ordering::Less];
+    let window = policy.removals + 1;
+
+    // for each level and direction: the fewest removals up to that level, and the level kept
+    // before it
+    let mut runs: Vec<[(usize, Option<usize>); 2]> = Vec::with_capacity(levels.len());
+    for (j, level) in levels.iter().enumerate() {
+        runs.push(DIRECTIONS.map(|_| (j, None)));
+        for (d, direction) in DIRECTIONS.into_iter().enumerate() {
+            for i in (j.saturating_sub(window)..j).rev() {
+                let removals = runs[i][d].0 + (j - i - 1);
+                if removals < runs[j][d].0 && policy.allows(levels[i], *level, direction) {
+                    runs[j][d] = (removals, Some(i));
+                }
+            }
+        }
+    }
+
+    let n = levels.len();
+    let (removals, last, d) = (n.saturating_sub(window)..n)
+        .flat_map(|j| (0..DIRECTIONS.len()).map(move |d| (j, d)))
+        .map(|(j, d)| (runs[j][d].0 + (n - 1 - j), j, d))
+        .min_by_key(|(removals, _, _)| *removals)?;
+    if removals > policy.removals {
+        return None;
+    }
+
+    let mut kept = vec![false; n];
+    let mut level = Some(last);
+    while let Some(j) = level {
+        kept[j] = true;
+        level = runs[j][d].1;
+    }
+
+    Some((0..n).filter(|j| !kept[*j]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(Day2::solve(EXAMPLE).unwrap(), (2, Some(4)));
+    }
+
+    #[test]
+    fn is_report_safe_requires_gradual_monotonic_levels() {
+        assert_eq!(is_report_safe(&[7, 6, 4, 2, 1]), Verdict::Safe);
+        assert_eq!(is_report_safe(&[1, 3, 6, 7, 9]), Verdict::Safe);
+        assert_eq!(
+            is_report_safe(&[1, 2, 7, 8, 9]),
+            Verdict::Unsafe(Problem::StepTooLarge { index: 2, step: 5 })
+        );
+        assert_eq!(
+            is_report_safe(&[1, 3, 2, 4, 5]),
+            Verdict::Unsafe(Problem::DirectionChange { index: 2 })
+        );
+        assert_eq!(
+            is_report_safe(&[8, 6, 4, 4, 1]),
+            Verdict::Unsafe(Problem::NoChange { index: 3 })
+        );
+    }
+
+    #[test]
+    fn problem_dampener_names_the_removed_level() {
+        assert_eq!(
+            is_report_safe_with_problem_dampener(&[1, 3, 2, 4, 5]),
+            Verdict::SafeWithoutLevels(vec![1])
+        );
+        assert_eq!(
+            is_report_safe_with_problem_dampener(&[8, 6, 4, 4, 1]),
+            Verdict::SafeWithoutLevels(vec![2])
+        );
+        assert_eq!(
+            is_report_safe_with_problem_dampener(&[9, 7, 6, 2, 1]),
+            Verdict::Unsafe(Problem::StepTooLarge { index: 3, step: 4 })
+        );
+        assert_eq!(
+            Day2::explain(&Day2::parse(EXAMPLE).unwrap(), 2)
+                .unwrap()
+                .lines()
+                .nth(3),
+            Some("report 4 (1 3 2 4 5): safe without the level at index 1")
+        );
+    }
+
+    #[test]
+    fn problem_dampener_follows_the_policy() {
+        let policy = SafetyPolicy {
+            removals: 2,
+            ..SafetyPolicy::STRICT
+        };
+        assert_eq!(
+            check_report(&[1, 9, 2, 8, 3], &policy),
+            Verdict::SafeWithoutLevels(vec![1, 3])
+        );
+        assert_eq!(
+            check_report(&[9, 1, 2, 3, 5, 6, 4], &policy),
+            Verdict::SafeWithoutLevels(vec![0, 6])
+        );
+        assert_eq!(
+            check_report(&[1, 9, 2, 8, 3, 7], &policy),
+            Verdict::Unsafe(Problem::StepTooLarge { index: 1, step: 8 })
+        );
+        assert_eq!(check_report(&[5, 4], &policy), Verdict::Safe);
+        assert_eq!(check_report(&[], &policy), Verdict::Safe);
+
+        let policy = SafetyPolicy {
+            min_step: 2,
+            max_step: 5,
+            removals: 0,
+        };
+        assert_eq!(check_report(&[1, 6, 8], &policy), Verdict::Safe);
+        assert_eq!(
+            check_report(&[1, 6, 7], &policy),
+            Verdict::Unsafe(Problem::StepTooSmall { index: 2, step: 1 })
+        );
+    }
+
+    #[test]
+    fn problem_dampener_matches_removing_each_level() {
+        let reports = Day2::parse(EXAMPLE).unwrap();
+        for levels in reports
+            .iter()
+            .chain([&vec![3, 1, 2, 3, 4], &vec![1, 2, 3, 4, 3]])
+        {
+            let brute_force = (0..levels.len()).any(|i| {
+                let mut levels = levels.clone();
+                levels.remove(i);
+                is_report_safe(&levels).is_safe()
+            });
+            assert_eq!(
+                is_report_safe_with_problem_dampener(levels).is_safe(),
+                brute_force || is_report_safe(levels).is_safe(),
+                "{:?}",
+                levels
+            );
+        }
+    }
+}
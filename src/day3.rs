@@ -0,0 +1,846 @@
+use crate::{
+    error::ParseError,
+    solution::{Answer, Solution},
+};
+use std::{
+    ascii, fmt,
+    io::{self, Read},
+    ops::Range,
+};
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Memory;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut memory = Memory::default();
+        let mut scanner = Scanner::new(input, &STANDARD_SIGNATURES);
+        while let Some(item) = scanner.next_or_near_miss() {
+            match item {
+                Ok(call) => memory.calls.push(call),
+                Err(near_miss) => memory.near_misses.push(near_miss),
+            }
+        }
+
+        Ok(memory)
+    }
+
+    fn part1(memory: &Self::Input) -> Answer {
+        run_standard(&memory.calls).all
+    }
+
+    fn part2(memory: &Self::Input) -> Option<Answer> {
+        Some(run_standard(&memory.calls).enabled)
+    }
+
+    fn explain(memory: &Self::Input, part: u8) -> Option<String> {
+        let mut lines = memory
+            .near_misses
+            .iter()
+            .map(|miss| {
+                let line = format!("{} rejected, {}", miss.text, miss.reason);
+                (miss.span.start, line)
+            })
+            .collect::<Vec<_>>();
+        if part == 2 {
+            lines.extend(memory.disabled_muls().into_iter().map(|disabled| {
+                let line = format!(
+                    "{} disabled by don't() at offset {}",
+                    disabled.token.instruction, disabled.by.start
+                );
+                (disabled.token.span.start, line)
+            }));
+        }
+
+        lines.sort();
+        Some(
+            lines
+                .into_iter()
+                .map(|(offset, line)| format!("offset {}: {}\n", offset, line))
+                .collect(),
+        )
+    }
+}
+
+/// The calls to the standard instructions read from corrupted memory, and the near misses
+/// skipped along the way.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Memory {
+    pub calls: Vec<Call>,
+    pub near_misses: Vec<NearMiss>,
+}
+
+impl Memory {
+    /// Every `mul` that was read but disabled by a `don't()` before it.
+    pub fn disabled_muls(&self) -> Vec<DisabledMul> {
+        let mut disabled = Vec::new();
+        let mut disabled_by = None;
+        for token in self.calls.iter().cloned().map(decode) {
+            match token.instruction {
+                Instruction::Mul(..) => {
+                    if let Some(by) = &disabled_by {
+                        disabled.push(DisabledMul {
+                            token,
+                            by: Range::clone(by),
+                        });
+                    }
+                }
+                Instruction::Do => disabled_by = None,
+                Instruction::Dont => disabled_by = Some(token.span),
+            }
+        }
+
+        disabled
+    }
+}
+
+/// An uncorrupted instruction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Instruction {
+    /// `mul(a,b)`, where each argument has one to three digits.
+    Mul(u64, u64),
+    /// `do()`
+    Do,
+    /// `don't()`
+    Dont,
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instruction::Mul(a, b) => write!(f, "mul({},{})", a, b),
+            Instruction::Do => write!(f, "do()"),
+            Instruction::Dont => write!(f, "don't()"),
+        }
+    }
+}
+
+/// An instruction and the bytes of memory it was read from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Token {
+    pub instruction: Instruction,
+    pub span: Range<usize>,
+}
+
+/// The name of an instruction and how many arguments it takes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Signature {
+    pub name: &'static str,
+    pub arity: usize,
+}
+
+/// The signatures of the instructions that [`Instruction`] covers, in the same order.
+const STANDARD_SIGNATURES: [Signature; 3] = [
+    Signature {
+        name: "mul",
+        arity: 2,
+    },
+    Signature {
+        name: "do",
+        arity: 0,
+    },
+    Signature {
+        name: "don't",
+        arity: 0,
+    },
+];
+
+/// Text that starts with the name of one of the instructions a [`Scanner`] looks for, but isn't
+/// a call to it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NearMiss {
+    /// The index of the instruction's signature.
+    pub instruction: usize,
+    /// The bytes of memory from the start of the name up to and including the first byte that
+    /// doesn't fit.
+    pub span: Range<usize>,
+    /// The bytes of the span, with anything that isn't UTF-8 replaced.
+    pub text: String,
+    pub reason: Rejection,
+}
+
+/// Why a near miss isn't a call.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Rejection {
+    /// Something other than `expected` was found, or the end of memory if `found` is `None`.
+    Expected {
+        expected: &'static str,
+        found: Option<u8>,
+    },
+    /// An argument has more than three digits.
+    TooManyDigits,
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rejection::Expected {
+                expected,
+                found: Some(byte),
+            } => write!(
+                f,
+                "expected {}, found `{}`",
+                expected,
+                ascii::escape_default(*byte)
+            ),
+            Rejection::Expected {
+                expected,
+                found: None,
+            } => write!(f, "expected {}, found end of memory", expected),
+            Rejection::TooManyDigits => write!(f, "argument has more than three digits"),
+        }
+    }
+}
+
+/// A call to one of the instructions a [`Scanner`] looks for.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Call {
+    /// The index of the instruction's signature.
+    pub instruction: usize,
+    pub arguments: Vec<u64>,
+    pub span: Range<usize>,
+}
+
+/// Reads calls to the instructions with the given signatures from corrupted memory, skipping
+/// everything else. A call is the instruction's name followed by its arguments in parentheses,
+/// separated by commas, and each argument has one to three digits.
+pub struct Scanner<'a> {
+    memory: &'a [u8],
+    position: usize,
+    signatures: &'a [Signature],
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(memory: &'a str, signatures: &'a [Signature]) -> Self {
+        Scanner {
+            memory: memory.as_bytes(),
+            position: 0,
+            signatures,
+        }
+    }
+}
+
+impl Scanner<'_> {
+    /// Reads the next call or near miss. Calls don't overlap, but near misses can overlap each
+    /// other and the calls after them.
+    pub fn next_or_near_miss(&mut self) -> Option<Result<Call, NearMiss>> {
+        while self.position < self.memory.len() {
+            let start = self.position;
+            match scan_at(&self.memory[start..], self.signatures) {
+                Scan::Call {
+                    instruction,
+                    arguments,
+                    length,
+                } => {
+                    self.position += length;
+                    return Some(Ok(Call {
+                        instruction,
+                        arguments,
+                        span: start..self.position,
+                    }));
+                }
+                Scan::NearMiss {
+                    instruction,
+                    reason,
+                    length,
+                } => {
+                    let span = start..start + length;
+                    self.position += 1;
+                    return Some(Err(NearMiss {
+                        instruction,
+                        text: String::from_utf8_lossy(&self.memory[span.clone()]).into_owned(),
+                        span,
+                        reason,
+                    }));
+                }
+                Scan::Nothing => self.position += 1,
+            }
+        }
+
+        None
+    }
+}
+
+impl Iterator for Scanner<'_> {
+    type Item = Call;
+
+    fn next(&mut self) -> Option<Call> {
+        while let Some(item) = self.next_or_near_miss() {
+            if let Ok(call) = item {
+                return Some(call);
+            }
+        }
+
+        None
+    }
+}
+
+/// How many bytes of memory to read at a time when streaming.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Reads calls like a [`Scanner`], but from memory read a chunk at a time, so that memory of any
+/// size can be scanned without holding more than a chunk of it.
+pub struct StreamScanner<'a, R> {
+    reader: R,
+    signatures: &'a [Signature],
+    /// The unscanned end of the last chunk and the chunk after it.
+    buffer: Vec<u8>,
+    /// Where scanning continues in the buffer.
+    position: usize,
+    /// The offset in memory of the start of the buffer.
+    offset: usize,
+    chunk_size: usize,
+    /// How many bytes after the position a call or near miss might need, which is kept in the
+    /// buffer until the end of memory, so that they aren't cut off at the end of a chunk.
+    lookahead: usize,
+    done: bool,
+}
+
+impl<'a, R: Read> StreamScanner<'a, R> {
+    pub fn new(reader: R, signatures: &'a [Signature]) -> Self {
+        StreamScanner::with_chunk_size(reader, signatures, CHUNK_SIZE)
+    }
+
+    pub fn with_chunk_size(reader: R, signatures: &'a [Signature], chunk_size: usize) -> Self {
+        StreamScanner {
+            reader,
+            signatures,
+            buffer: Vec::new(),
+            position: 0,
+            offset: 0,
+            chunk_size: chunk_size.max(1),
+            // the name, the parentheses, and up to four digits (the fourth to reject) and a
+            // comma for each argument
+            lookahead: signatures
+                .iter()
+                .map(|s| s.name.len() + 2 + 5 * s.arity)
+                .max()
+                .unwrap_or(0),
+            done: false,
+        }
+    }
+
+    /// Reads chunks until the lookahead is buffered or memory ends.
+    fn fill(&mut self) -> io::Result<()> {
+        while !self.done && self.buffer.len() - self.position < self.lookahead {
+            self.buffer.drain(..self.position);
+            self.offset += self.position;
+            self.position = 0;
+
+            let filled = self.buffer.len();
+            self.buffer.resize(filled + self.chunk_size, 0);
+            let read = self.reader.read(&mut self.buffer[filled..]);
+            self.buffer.truncate(filled + *read.as_ref().unwrap_or(&0));
+            match read {
+                Ok(0) => self.done = true,
+                Ok(_) => {}
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => {
+                    self.done = true;
+                    self.buffer.clear();
+                    self.position = 0;
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: Read> Iterator for StreamScanner<'_, R> {
+    type Item = io::Result<Call>;
+
+    fn next(&mut self) -> Option<io::Result<Call>> {
+        loop {
+            if let Err(e) = self.fill() {
+                return Some(Err(e));
+            }
+
+            if self.position == self.buffer.len() {
+                return None;
+            }
+
+            let start = self.offset + self.position;
+            match scan_at(&self.buffer[self.position..], self.signatures) {
+                Scan::Call {
+                    instruction,
+                    arguments,
+                    length,
+                } => {
+                    self.position += length;
+                    return Some(Ok(Call {
+                        instruction,
+                        arguments,
+                        span: start..start + length,
+                    }));
+                }
+                Scan::NearMiss { .. } | Scan::Nothing => self.position += 1,
+            }
+        }
+    }
+}
+
+/// What a scanner finds at one position in memory.
+enum Scan {
+    Call {
+        instruction: usize,
+        arguments: Vec<u64>,
+        length: usize,
+    },
+    NearMiss {
+        instruction: usize,
+        reason: Rejection,
+        length: usize,
+    },
+    Nothing,
+}
+
+/// Looks for a call to any of the instructions with `signatures` at the start of `bytes`. The
+/// first instruction with a call wins, and otherwise the near miss that got furthest, so that
+/// `don't(x)` is blamed on `don't` rather than on `do`.
+fn scan_at(bytes: &[u8], signatures: &[Signature]) -> Scan {
+    let mut scan = Scan::Nothing;
+    for (instruction, signature) in signatures.iter().enumerate() {
+        match read_call(bytes, signature) {
+            Some(Ok((arguments, length))) => {
+                return Scan::Call {
+                    instruction,
+                    arguments,
+                    length,
+                }
+            }
+            Some(Err((reason, length))) => {
+                let further = match scan {
+                    Scan::NearMiss {
+                        length: furthest, ..
+                    } => length > furthest,
+                    _ => true,
+                };
+                if further {
+                    scan = Scan::NearMiss {
+                        instruction,
+                        reason,
+                        length,
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    scan
+}
+
+/// Reads the uncorrupted [`Instruction`]s from corrupted memory, skipping everything else.
+pub struct Lexer<'a>(Scanner<'a>);
+
+impl<'a> Lexer<'a> {
+    pub fn new(memory: &'a str) -> Self {
+        Lexer(Scanner::new(memory, &STANDARD_SIGNATURES))
+    }
+}
+
+impl Iterator for Lexer<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        self.0.next().map(decode)
+    }
+}
+
+/// Turns a call read with the standard signatures into a token.
+fn decode(call: Call) -> Token {
+    let instruction = match (call.instruction, &call.arguments[..]) {
+        (0, [a, b]) => Instruction::Mul(*a, *b),
+        (1, []) => Instruction::Do,
+        (2, []) => Instruction::Dont,
+        _ => unreachable!("the scanner only reads the standard instructions"),
+    };
+
+    Token {
+        instruction,
+        span: call.span,
+    }
+}
+
+/// A rejection and the bytes from the one that caused it onwards.
+type Corruption<'a> = (Rejection, &'a [u8]);
+
+/// A call's arguments and length in bytes, or why it isn't a call and the length of the near
+/// miss.
+type CallOrNearMiss = Result<(Vec<u64>, usize), (Rejection, usize)>;
+
+/// Reads a call to the instruction with `signature` from the start of `bytes`, returning its
+/// arguments and its length in bytes. Returns `None` if `bytes` doesn't start with the
+/// instruction's name, or why it isn't a call and the length up to and including the byte that
+/// doesn't fit if it does.
+fn read_call(bytes: &[u8], signature: &Signature) -> Option<CallOrNearMiss> {
+    let rest = bytes.strip_prefix(signature.name.as_bytes())?;
+    let read = || -> Result<(Vec<u64>, &[u8]), Corruption<'_>> {
+        let mut rest = expect(rest, b'(', "`(`")?;
+        let mut arguments = Vec::with_capacity(signature.arity);
+        for i in 0..signature.arity {
+            if i > 0 {
+                rest = expect(rest, b',', "`,`")?;
+            }
+            let (argument, after) = read_argument(rest)?;
+            arguments.push(argument);
+            rest = after;
+        }
+
+        Ok((arguments, expect(rest, b')', "`)`")?))
+    };
+
+    Some(match read() {
+        Ok((arguments, rest)) => Ok((arguments, bytes.len() - rest.len())),
+        Err((reason, rest)) => Err((reason, (bytes.len() - rest.len() + 1).min(bytes.len()))),
+    })
+}
+
+/// Reads `byte` from the start of `bytes`, returning the bytes after it.
+fn expect<'a>(
+    bytes: &'a [u8],
+    byte: u8,
+    expected: &'static str,
+) -> Result<&'a [u8], Corruption<'a>> {
+    match bytes.split_first() {
+        Some((b, rest)) if *b == byte => Ok(rest),
+        found => Err((
+            Rejection::Expected {
+                expected,
+                found: found.map(|(b, _)| *b),
+            },
+            bytes,
+        )),
+    }
+}
+
+/// Reads a number of one to three digits from the start of `bytes`, returning it and the bytes
+/// after it.
+fn read_argument(bytes: &[u8]) -> Result<(u64, &[u8]), Corruption<'_>> {
+    let digits = bytes
+        .iter()
+        .take(4)
+        .take_while(|b| b.is_ascii_digit())
+        .count();
+    match digits {
+        0 => Err((
+            Rejection::Expected {
+                expected: "a number",
+                found: bytes.first().copied(),
+            },
+            bytes,
+        )),
+        4 => Err((Rejection::TooManyDigits, &bytes[3..])),
+        _ => {
+            let value = bytes[..digits]
+                .iter()
+                .fold(0, |value, digit| value * 10 + (digit - b'0') as u64);
+            Ok((value, &bytes[digits..]))
+        }
+    }
+}
+
+/// A `mul` that was read but didn't count, because a `don't()` came before it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DisabledMul {
+    pub token: Token,
+    /// The span of the `don't()` that disabled it.
+    pub by: Range<usize>,
+}
+
+/// What an instruction does to the state of a [`Vm`], given its arguments.
+pub type Operation<S> = fn(&mut S, &[u64]);
+
+/// Runs the instructions in corrupted memory against some state `S`, from a table of instruction
+/// kinds registered by name.
+pub struct Vm<S> {
+    signatures: Vec<Signature>,
+    operations: Vec<Operation<S>>,
+}
+
+impl<S> Vm<S> {
+    /// A machine that doesn't know any instructions yet.
+    pub fn new() -> Self {
+        Vm {
+            signatures: Vec::new(),
+            operations: Vec::new(),
+        }
+    }
+
+    /// Registers an instruction called `name` that takes `arity` arguments, replacing any
+    /// instruction already registered with that name.
+    pub fn register(mut self, name: &'static str, arity: usize, operation: Operation<S>) -> Self {
+        let signature = Signature { name, arity };
+        match self.signatures.iter().position(|s| s.name == name) {
+            Some(i) => {
+                self.signatures[i] = signature;
+                self.operations[i] = operation;
+            }
+            None => {
+                self.signatures.push(signature);
+                self.operations.push(operation);
+            }
+        }
+        self
+    }
+
+    /// Runs every registered instruction found in `memory` against `state`, in order.
+    pub fn run(&self, memory: &str, state: &mut S) {
+        for call in Scanner::new(memory, &self.signatures) {
+            self.execute(&call, state);
+        }
+    }
+
+    /// Like [`Vm::run`], but reading memory from `reader` a chunk at a time.
+    pub fn run_reader(&self, reader: impl Read, state: &mut S) -> io::Result<()> {
+        for call in StreamScanner::new(reader, &self.signatures) {
+            self.execute(&call?, state);
+        }
+
+        Ok(())
+    }
+
+    /// Runs a single call, read with this machine's signatures, against `state`.
+    pub fn execute(&self, call: &Call, state: &mut S) {
+        (self.operations[call.instruction])(state, &call.arguments);
+    }
+}
+
+impl<S> Default for Vm<S> {
+    fn default() -> Self {
+        Vm::new()
+    }
+}
+
+/// The state of the standard instruction set, which keeps the answers to both parts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Machine {
+    /// The sum of every multiplication.
+    pub all: u64,
+    /// The sum of the multiplications that weren't disabled.
+    pub enabled: u64,
+    pub mul_enabled: bool,
+}
+
+impl Default for Machine {
+    fn default() -> Self {
+        Machine {
+            all: 0,
+            enabled: 0,
+            mul_enabled: true,
+        }
+    }
+}
+
+impl Vm<Machine> {
+    /// The instructions from the puzzle, registered in the same order as the standard
+    /// signatures: `mul(a,b)` adds `a * b` to the sums, or only to the sum of every multiplication
+    /// while disabled, `do()` enables it and `don't()` disables it.
+    pub fn standard() -> Self {
+        Self::new()
+            .register("mul", 2, |machine, arguments| {
+                let product = arguments[0] * arguments[1];
+                machine.all += product;
+                if machine.mul_enabled {
+                    machine.enabled += product;
+                }
+            })
+            .register("do", 0, |machine, _| machine.mul_enabled = true)
+            .register("don't", 0, |machine, _| machine.mul_enabled = false)
+    }
+}
+
+/// Runs calls read with the standard signatures on the standard machine.
+fn run_standard(calls: &[Call]) -> Machine {
+    let vm = Vm::standard();
+    let mut machine = Machine::default();
+    for call in calls {
+        vm.execute(call, &mut machine);
+    }
+    machine
+}
+
+/// Solves both parts in a single pass over memory read from `reader` a chunk at a time.
+pub fn solve_reader(reader: &mut dyn Read) -> io::Result<(Answer, Option<Answer>)> {
+    let mut machine = Machine::default();
+    Vm::standard().run_reader(reader, &mut machine)?;
+    Ok((machine.all, Some(machine.enabled)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let memory =
+            Day3::parse("xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))")
+                .unwrap();
+        assert_eq!(Day3::part1(&memory), 161);
+
+        let memory = Day3::parse(
+            "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))",
+        )
+        .unwrap();
+        assert_eq!(Day3::part2(&memory), Some(48));
+    }
+
+    #[test]
+    fn vm_runs_registered_instructions() {
+        assert_eq!(Vm::standard().signatures, STANDARD_SIGNATURES);
+
+        let memory = "mul(2,3)don't()add(4,5)mul(1,1)don't()do()neg(7)do()mul(3,3)neg(1,2)";
+
+        let mut machine = Machine::default();
+        Vm::standard().run(memory, &mut machine);
+        assert_eq!((machine.all, machine.enabled), (16, 15));
+
+        let mut machine = Machine::default();
+        Vm::standard().run(
+            "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))",
+            &mut machine,
+        );
+        assert_eq!((machine.all, machine.enabled), (161, 48));
+
+        /// A running total that can go negative, and how many `don't()`s are still in effect.
+        #[derive(Default)]
+        struct Nested {
+            total: i64,
+            depth: usize,
+        }
+
+        // don't() nests, so it takes as many do()s to enable the machine again
+        let mut nested = Nested::default();
+        Vm::<Nested>::new()
+            .register("mul", 2, |nested, arguments| {
+                if nested.depth == 0 {
+                    nested.total += (arguments[0] * arguments[1]) as i64;
+                }
+            })
+            .register("add", 2, |nested, arguments| {
+                nested.total += (arguments[0] + arguments[1]) as i64
+            })
+            .register("neg", 1, |nested, arguments| {
+                nested.total -= arguments[0] as i64
+            })
+            .register("don't", 0, |nested, _| nested.depth += 1)
+            .register("do", 0, |nested, _| {
+                nested.depth = nested.depth.saturating_sub(1)
+            })
+            .run(memory, &mut nested);
+        assert_eq!(nested.total, 6 + 9 - 7 + 9);
+    }
+
+    #[test]
+    fn near_misses_and_disabled_muls() {
+        let memory =
+            Day3::parse("mul(2,3]mul ( 2,3)mul(1234,5)don't()mul(,4)mul(5,6)do()don't(x)mul(7")
+                .unwrap();
+        assert_eq!(
+            memory
+                .near_misses
+                .iter()
+                .map(|miss| (
+                    miss.span.clone(),
+                    miss.text.as_str(),
+                    miss.reason.to_string()
+                ))
+                .collect::<Vec<_>>(),
+            [
+                (0..8, "mul(2,3]", "expected `)`, found `]`".to_string()),
+                (8..12, "mul ", "expected `(`, found ` `".to_string()),
+                (
+                    18..26,
+                    "mul(1234",
+                    "argument has more than three digits".to_string()
+                ),
+                (36..41, "mul(,", "expected a number, found `,`".to_string()),
+                (55..62, "don't(x", "expected `)`, found `x`".to_string()),
+                (
+                    63..68,
+                    "mul(7",
+                    "expected `,`, found end of memory".to_string()
+                ),
+            ]
+        );
+        assert_eq!(
+            memory.disabled_muls(),
+            [DisabledMul {
+                token: Token {
+                    instruction: Instruction::Mul(5, 6),
+                    span: 43..51
+                },
+                by: 29..36
+            }]
+        );
+
+        assert_eq!(
+            Day3::explain(&memory, 2).unwrap().lines().nth(4),
+            Some("offset 43: mul(5,6) disabled by don't() at offset 29")
+        );
+    }
+
+    /// Hands out memory one byte per read, so that every instruction is split across chunks.
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.0.len().min(buf.len()).min(1);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn stream_scanner_reads_calls_split_across_chunks() {
+        let memory =
+            "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(1234,8)undo()?mul(8,5))mul(12";
+        let calls = Scanner::new(memory, &STANDARD_SIGNATURES).collect::<Vec<_>>();
+        for chunk_size in [1, 2, 7, 100] {
+            let streamed =
+                StreamScanner::with_chunk_size(memory.as_bytes(), &STANDARD_SIGNATURES, chunk_size)
+                    .collect::<io::Result<Vec<_>>>()
+                    .unwrap();
+            assert_eq!(streamed, calls, "chunk size {}", chunk_size);
+        }
+
+        let streamed =
+            StreamScanner::with_chunk_size(Trickle(memory.as_bytes()), &STANDARD_SIGNATURES, 4)
+                .collect::<io::Result<Vec<_>>>()
+                .unwrap();
+        assert_eq!(streamed, calls);
+
+        assert_eq!(
+            solve_reader(&mut Trickle(memory.as_bytes())).unwrap(),
+            (73, Some(48))
+        );
+        assert_eq!(solve_reader(&mut &b"mul(1"[..]).unwrap(), (0, Some(0)));
+    }
+
+    #[test]
+    fn lexer_reads_instructions_with_spans() {
+        let tokens =
+            Lexer::new("mul(1234,5)mul(123,4)don't()xdo()mul(1,2 mul(12").collect::<Vec<_>>();
+        assert_eq!(
+            tokens,
+            [
+                Token {
+                    instruction: Instruction::Mul(123, 4),
+                    span: 11..21
+                },
+                Token {
+                    instruction: Instruction::Dont,
+                    span: 21..28
+                },
+                Token {
+                    instruction: Instruction::Do,
+                    span: 29..33
+                },
+            ]
+        );
+    }
+}
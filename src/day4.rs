@@ -0,0 +1,240 @@
+use crate::{
+    direction::Direction8,
+    error::ParseError,
+    grid::{Grid, Position},
+    solution::{Answer, Solution},
+};
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::parse_chars(input)
+    }
+
+    fn part1(word_search: &Self::Input) -> Answer {
+        sum_xmas_words(word_search) as Answer
+    }
+
+    fn part2(word_search: &Self::Input) -> Option<Answer> {
+        Some(sum_mas_in_the_shape_of_an_x(word_search) as Answer)
+    }
+}
+
+/// The directions a word search reads words in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Directions {
+    /// Up, down, left and right.
+    Orthogonal,
+    Diagonal,
+    /// Both orthogonal and diagonal.
+    All,
+}
+
+impl Directions {
+    pub fn directions(self) -> &'static [Direction8] {
+        match self {
+            Directions::Orthogonal => &Direction8::ORTHOGONALS,
+            Directions::Diagonal => &Direction8::DIAGONALS,
+            Directions::All => &Direction8::ALL,
+        }
+    }
+}
+
+/// How to search for words.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SearchOptions {
+    pub directions: Directions,
+    /// Whether words can run off one edge of the grid and continue from the opposite edge.
+    pub wrap: bool,
+}
+
+/// A word found in a word search.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Match<'a> {
+    pub word: &'a str,
+    /// The position of the word's first letter.
+    pub start: Position,
+    pub direction: Direction8,
+}
+
+/// Finds every occurrence of each of `words` in `word_search`, ordered by start position, then
+/// by word, then clockwise by direction from up. A word that reads the same both ways is found
+/// once in each direction, a single letter is found once, in the first of the directions, and
+/// empty words aren't found at all. When wrapping, a word can't run all the way around the grid
+/// back into the cell it started from.
+pub fn find_words<'a>(
+    word_search: &Grid<char>,
+    words: &[&'a str],
+    options: SearchOptions,
+) -> Vec<Match<'a>> {
+    let step = |position, direction| {
+        if options.wrap {
+            Some(word_search.wrapping_step(position, direction))
+        } else {
+            word_search.step(position, direction)
+        }
+    };
+
+    let mut matches = Vec::new();
+    for (start, letter) in word_search.iter() {
+        for word in words {
+            let mut letters = word.chars();
+            if letters.next() != Some(*letter) {
+                continue;
+            }
+
+            // a single letter reads the same in every direction
+            let directions = match letters.as_str() {
+                "" => &options.directions.directions()[..1],
+                _ => options.directions.directions(),
+            };
+
+            for direction in directions {
+                let mut position = start;
+                let found = letters.clone().all(|c| match step(position, *direction) {
+                    Some(next) if next != start && word_search[next] == c => {
+                        position = next;
+                        true
+                    }
+                    _ => false,
+                });
+
+                if found {
+                    matches.push(Match {
+                        word,
+                        start,
+                        direction: *direction,
+                    });
+                }
+            }
+        }
+    }
+
+    matches
+}
+
+pub fn sum_xmas_words(word_search: &Grid<char>) -> usize {
+    let options = SearchOptions {
+        directions: Directions::All,
+        wrap: false,
+    };
+    find_words(word_search, &["XMAS"], options).len()
+}
+
+pub fn sum_mas_in_the_shape_of_an_x(word_search: &Grid<char>) -> usize {
+    let mut sum_x_mas = 0;
+
+    // visit each cell in the grid looking for an 'A' character
+    for (i, j) in word_search.positions_of(&'A') {
+        let corner =
+            |direction: Direction8| word_search.step((i, j), direction).map(|p| word_search[p]);
+
+        // Found an 'A', now search for any of the following patterns:
+        // (top) M M  (left) M S  (bottom) S S  (right) S M
+        //        A           A             A            A
+        //       S S         M S           M M          S M
+        //
+        // i.e. each diagonal through the 'A' reads "MAS" in one direction or the other
+        let is_mas = |a, b| matches!((a, b), (Some('M'), Some('S')) | (Some('S'), Some('M')));
+        if is_mas(corner(Direction8::UpLeft), corner(Direction8::DownRight))
+            && is_mas(corner(Direction8::UpRight), corner(Direction8::DownLeft))
+        {
+            sum_x_mas += 1;
+        }
+    }
+
+    sum_x_mas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(Day4::solve(EXAMPLE).unwrap(), (18, Some(9)));
+    }
+
+    #[test]
+    fn find_words_in_direction_sets() {
+        let word_search = Grid::parse_chars(
+            "CAT
+XOX
+TAC
+",
+        )
+        .unwrap();
+        let find = |words: &[&'static str], directions, wrap| {
+            find_words(&word_search, words, SearchOptions { directions, wrap })
+                .into_iter()
+                .map(|m| (m.word, m.start, m.direction))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            find(&["CAT", "COC"], Directions::All, false),
+            [
+                ("CAT", (0, 0), Direction8::Right),
+                ("COC", (0, 0), Direction8::DownRight),
+                ("CAT", (2, 2), Direction8::Left),
+                ("COC", (2, 2), Direction8::UpLeft),
+            ]
+        );
+        assert_eq!(find(&["CAT"], Directions::Diagonal, false).len(), 0);
+        assert_eq!(
+            find(&["ATC"], Directions::Orthogonal, true),
+            [
+                ("ATC", (0, 1), Direction8::Right),
+                ("ATC", (2, 1), Direction8::Left),
+            ]
+        );
+        assert_eq!(find(&["ATC"], Directions::Orthogonal, false).len(), 0);
+        assert_eq!(find(&["XX", ""], Directions::Orthogonal, true).len(), 2);
+        assert_eq!(
+            find(&["O"], Directions::All, true),
+            [("O", (1, 1), Direction8::Up)]
+        );
+        assert_eq!(
+            find(&["O"], Directions::Diagonal, false),
+            [("O", (1, 1), Direction8::UpRight)]
+        );
+    }
+
+    #[test]
+    fn wrapped_words_stop_at_their_first_letter() {
+        let word_search = Grid::parse_chars("AB\n").unwrap();
+        let find = |word| {
+            let options = SearchOptions {
+                directions: Directions::Orthogonal,
+                wrap: true,
+            };
+            find_words(&word_search, &[word], options)
+                .into_iter()
+                .map(|m| (m.start, m.direction))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(find("ABAB"), []);
+        assert_eq!(find("ABA"), []);
+        assert_eq!(
+            find("AB"),
+            [((0, 0), Direction8::Right), ((0, 0), Direction8::Left)]
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{
+    error::{end_of, parse_number, ParseError},
+    solution::{Answer, Solution},
+};
+use std::collections::HashMap;
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = PrintQueue;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let lines = input.lines();
+        let mut page_ordering_rules = true;
+        let mut page_order: HashMap<u64, Vec<u64>> = HashMap::new();
+        let mut updates: Vec<Vec<u64>> = Vec::new();
+
+        for l in lines {
+            if l.is_empty() {
+                // found delimiter between page ordering rules and updates
+                page_ordering_rules = false;
+            } else if page_ordering_rules {
+                // parse page ordering rules, in the form "X|Y" where page #X comes before page #Y
+                let (before, after) = l
+                    .split_once('|')
+                    .ok_or_else(|| ParseError::at(input, end_of(l), "`|` between page numbers"))?;
+                let before: u64 = parse_number(input, before)?;
+                let after: u64 = parse_number(input, after)?;
+
+                // Build a hash map with page numbers as keys, and with the list of page numbers
+                // that come before the key page number as values.
+                page_order.entry(after).or_default().push(before);
+            } else {
+                // Parse updates in the form "X, Y, ..." where X, Y, ... are page numbers
+                updates.push(
+                    l.split(',')
+                        .map(|part| parse_number(input, part))
+                        .collect::<Result<_, _>>()?,
+                );
+            }
+        }
+
+        Ok(PrintQueue {
+            page_order,
+            updates,
+        })
+    }
+
+    fn part1(print_queue: &Self::Input) -> Answer {
+        sum_middle_page_numbers_in_correctly_ordered_updates(print_queue)
+    }
+
+    fn part2(print_queue: &Self::Input) -> Option<Answer> {
+        Some(sum_middle_page_numbers_in_incorrectly_ordered_updates(
+            print_queue,
+        ))
+    }
+}
+
+pub struct PrintQueue {
+    pub page_order: HashMap<u64, Vec<u64>>,
+    pub updates: Vec<Vec<u64>>,
+}
+
+#[derive(PartialEq)]
+pub enum UpdateTypes {
+    OnlyCorrect,
+    OnlyFixed,
+}
+
+pub fn sum_middle_page_numbers_in_ordered_updates(
+    print_queue: &PrintQueue,
+    update_type: UpdateTypes,
+) -> u64 {
+    let page_order = &print_queue.page_order;
+    let mut keep_updates: Vec<Vec<u64>> = Vec::new();
+
+    for parsed_updates in &print_queue.updates {
+        // Build a new update that adheres to page ordering rules using the page numbers from
+        // the parsed update. Discard the parsed update if it doesn't match what we build here.
+        let mut new_updates = Vec::new();
+        for u in parsed_updates {
+            if new_updates.is_empty() {
+                new_updates.push(*u);
+            } else {
+                let mut inserted = false;
+                for (i, new_u) in new_updates.iter().enumerate() {
+                    if page_order
+                        .get(new_u)
+                        .is_some_and(|before| before.contains(u))
+                    {
+                        new_updates.insert(i, *u);
+                        inserted = true;
+                        break;
+                    }
+                }
+
+                if !inserted {
+                    new_updates.push(*u);
+                }
+            }
+        }
+
+        assert!(parsed_updates.len() == new_updates.len());
+        if update_type == UpdateTypes::OnlyCorrect && *parsed_updates == new_updates {
+            keep_updates.push(parsed_updates.clone());
+        } else if update_type == UpdateTypes::OnlyFixed && *parsed_updates != new_updates {
+            keep_updates.push(new_updates);
+        }
+    }
+
+    keep_updates
+        .iter()
+        .map(|u| u.get(u.len() / 2).unwrap())
+        .sum()
+}
+
+pub fn sum_middle_page_numbers_in_correctly_ordered_updates(print_queue: &PrintQueue) -> u64 {
+    sum_middle_page_numbers_in_ordered_updates(print_queue, UpdateTypes::OnlyCorrect)
+}
+
+pub fn sum_middle_page_numbers_in_incorrectly_ordered_updates(print_queue: &PrintQueue) -> u64 {
+    sum_middle_page_numbers_in_ordered_updates(print_queue, UpdateTypes::OnlyFixed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(Day5::solve(EXAMPLE).unwrap(), (143, Some(123)));
+    }
+}
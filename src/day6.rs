@@ -0,0 +1,284 @@
+use crate::{
+    direction::Direction,
+    error::{end_of, ParseError},
+    grid::Grid,
+    image::{heat, Image},
+    render::{Color, Render},
+    solution::{Answer, Solution},
+};
+use std::collections::HashMap;
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let lab_map = Grid::parse_chars(input)?;
+        let Some(guard) = find_guard(&lab_map) else {
+            return Err(ParseError::at(input, end_of(input), "a guard `^`"));
+        };
+
+        let start = GuardPosition::new(guard, Direction::Up);
+        if let GuardRouteOutcome::LoopDetected = calulate_guard_route(&start, &lab_map) {
+            let offset = input.find('^').expect("the guard was found in the map");
+            return Err(ParseError::at(
+                input,
+                &input[offset..offset + 1],
+                "a guard who leaves the lab",
+            ));
+        }
+
+        Ok(lab_map)
+    }
+
+    fn part1(lab_map: &Self::Input) -> Answer {
+        sum_visited_guard_positions(lab_map) as Answer
+    }
+
+    fn part2(lab_map: &Self::Input) -> Option<Answer> {
+        Some(sum_candidate_obstacle_positions(lab_map) as Answer)
+    }
+
+    fn render(lab_map: &Self::Input, part: u8) -> Option<Render> {
+        let render = Render::new(lab_map, |c| *c);
+        if part == 2 {
+            let obstacles = find_candidate_obstacle_positions(lab_map);
+            return Some(render.highlight(obstacles, Some('O'), Color::Red));
+        }
+
+        let route = guard_route(lab_map)?;
+        Some(render.path(
+            route.iter().map(|p| (p.coordinates, p.direction)),
+            Color::Green,
+        ))
+    }
+
+    fn image(lab_map: &Self::Input, part: u8) -> Option<Image> {
+        if part == 2 {
+            return None;
+        }
+
+        // the guard can visit a position once in each direction
+        let mut visits: HashMap<(usize, usize), usize> = HashMap::new();
+        for position in guard_route(lab_map)? {
+            *visits.entry(position.coordinates).or_default() += 1;
+        }
+
+        Some(Image::from_grid(lab_map, |p, c| match c {
+            '#' => [70, 90, 160],
+            _ => heat(visits.get(&p).copied().unwrap_or(0), 4),
+        }))
+    }
+}
+
+/// The guard's route from their starting position, or `None` if it never leaves the lab.
+fn guard_route(lab_map: &Grid<char>) -> Option<Vec<GuardPosition>> {
+    let guard = GuardPosition::new(find_guard(lab_map)?, Direction::Up);
+    match calulate_guard_route(&guard, lab_map) {
+        GuardRouteOutcome::Positions(route) => Some(route),
+        GuardRouteOutcome::LoopDetected => None,
+    }
+}
+
+pub fn find_guard(lab_map: &Grid<char>) -> Option<(usize, usize)> {
+    lab_map.find(&'^')
+}
+
+pub enum PatrolProtocolOutcome {
+    Move((usize, usize)),
+    Turn(Direction),
+    Exit,
+}
+
+pub fn patrol_protocol(
+    i: &usize,
+    j: &usize,
+    dir: &Direction,
+    lab_map: &Grid<char>,
+) -> PatrolProtocolOutcome {
+    // the guard attempts to move one step forward
+    let Some((new_i, new_j)) = lab_map.step((*i, *j), *dir) else {
+        // the gaurd exits the bounds of the lab
+        return PatrolProtocolOutcome::Exit;
+    };
+
+    if lab_map[(new_i, new_j)] == '#' {
+        // the guard encounters an obstacle, turns right 90 degrees
+        PatrolProtocolOutcome::Turn(dir.turn_right())
+    } else {
+        PatrolProtocolOutcome::Move((new_i, new_j))
+    }
+}
+
+pub enum GuardRouteOutcome {
+    Positions(Vec<GuardPosition>),
+    LoopDetected,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct GuardPosition {
+    pub coordinates: (usize, usize),
+    pub direction: Direction,
+}
+
+impl GuardPosition {
+    pub fn new(coords: (usize, usize), dir: Direction) -> Self {
+        GuardPosition {
+            coordinates: coords,
+            direction: dir,
+        }
+    }
+}
+
+pub fn calulate_guard_route(pos: &GuardPosition, lab_map: &Grid<char>) -> GuardRouteOutcome {
+    let (mut i, mut j) = pos.coordinates;
+    let mut dir = pos.direction;
+    let mut positions: Vec<GuardPosition> = vec![*pos];
+    // a guard boxed in by obstacles turns on the spot forever without moving
+    let mut turns = 0;
+
+    loop {
+        match patrol_protocol(&i, &j, &dir, lab_map) {
+            PatrolProtocolOutcome::Move((new_i, new_j)) => {
+                i = new_i;
+                j = new_j;
+                turns = 0;
+                match positions
+                    .binary_search_by(|p| p.coordinates.cmp(&(i, j)).then(p.direction.cmp(&dir)))
+                {
+                    Ok(_) => return GuardRouteOutcome::LoopDetected,
+                    Err(index) => positions.insert(index, GuardPosition::new((i, j), dir)),
+                }
+            }
+            PatrolProtocolOutcome::Turn(direction) => {
+                dir = direction;
+                turns += 1;
+                if turns == 4 {
+                    return GuardRouteOutcome::LoopDetected;
+                }
+            }
+            PatrolProtocolOutcome::Exit => return GuardRouteOutcome::Positions(positions),
+        }
+    }
+}
+
+pub fn sum_visited_guard_positions(lab_map: &Grid<char>) -> usize {
+    let (i, j) = find_guard(lab_map).unwrap();
+    let outcome = calulate_guard_route(&GuardPosition::new((i, j), Direction::Up), lab_map);
+    if let GuardRouteOutcome::Positions(positions) = outcome {
+        // remove duplicates coordinates to get distinct positions the guard visited (regarless of direction)
+        let mut coordinates = positions
+            .into_iter()
+            .map(|p| p.coordinates)
+            .collect::<Vec<(usize, usize)>>();
+        coordinates.sort();
+        coordinates.dedup();
+
+        coordinates.len()
+    } else {
+        unreachable!("parsing checks that the guard leaves the lab")
+    }
+}
+
+pub fn sum_candidate_obstacle_positions(lab_map: &Grid<char>) -> usize {
+    find_candidate_obstacle_positions(lab_map).len()
+}
+
+/// Returns the positions where a single new obstacle would trap the guard in a loop.
+pub fn find_candidate_obstacle_positions(lab_map: &Grid<char>) -> Vec<(usize, usize)> {
+    let (original_i, original_j) = find_guard(lab_map).unwrap();
+    let mut i = original_i;
+    let mut j = original_j;
+    let mut dir = Direction::Up;
+    let mut obstacles: Vec<(usize, usize)> = vec![];
+
+    loop {
+        match patrol_protocol(&i, &j, &dir, lab_map) {
+            PatrolProtocolOutcome::Move((new_i, new_j)) => {
+                i = new_i;
+                j = new_j;
+
+                // add a candidate obstacle at the current position and look for an infinite loop
+                let mut altered_lab_map = lab_map.clone();
+                assert!(altered_lab_map[(i, j)] != '#');
+                altered_lab_map[(i, j)] = '#';
+                let outcome = calulate_guard_route(
+                    &GuardPosition::new((original_i, original_j), Direction::Up),
+                    &altered_lab_map,
+                );
+
+                if let GuardRouteOutcome::LoopDetected = outcome {
+                    match obstacles.binary_search(&(i, j)) {
+                        Ok(_) => (),
+                        Err(index) => obstacles.insert(index, (i, j)),
+                    }
+                }
+            }
+            PatrolProtocolOutcome::Turn(direction) => dir = direction,
+            PatrolProtocolOutcome::Exit => break,
+        }
+    }
+
+    obstacles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(Day6::solve(EXAMPLE).unwrap(), (41, Some(6)));
+    }
+
+    #[test]
+    fn patrol_protocol_moves_turns_and_exits() {
+        let lab_map = Day6::parse(EXAMPLE).unwrap();
+        assert!(matches!(
+            patrol_protocol(&6, &4, &Direction::Up, &lab_map),
+            PatrolProtocolOutcome::Move((5, 4))
+        ));
+        assert!(matches!(
+            patrol_protocol(&1, &4, &Direction::Up, &lab_map),
+            PatrolProtocolOutcome::Turn(Direction::Right)
+        ));
+        assert!(matches!(
+            patrol_protocol(&9, &7, &Direction::Down, &lab_map),
+            PatrolProtocolOutcome::Exit
+        ));
+    }
+
+    #[test]
+    fn guards_who_never_leave_are_rejected() {
+        let boxed_in = ".#.\n#^#\n.#.\n";
+        let lab_map = Grid::parse_chars(boxed_in).unwrap();
+        assert!(matches!(
+            calulate_guard_route(&GuardPosition::new((1, 1), Direction::Up), &lab_map),
+            GuardRouteOutcome::LoopDetected
+        ));
+        assert_eq!(
+            Day6::parse(boxed_in).unwrap_err().to_string(),
+            "2:2: expected a guard who leaves the lab, found `^`"
+        );
+
+        let looping = ".#..\n...#\n#^..\n..#.\n";
+        assert_eq!(
+            Day6::parse(looping).unwrap_err().to_string(),
+            "3:2: expected a guard who leaves the lab, found `^`"
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{
+    error::{end_of, parse_number, ParseError},
+    solution::{Answer, Solution},
+};
+use itertools::Itertools;
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<(u64, Vec<u64>)>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .lines()
+            .map(|l| {
+                let (value, operands) = l
+                    .split_once(':')
+                    .ok_or_else(|| ParseError::at(input, end_of(l), "`:` after the test value"))?;
+                let value = parse_number(input, value)?;
+                let operands: Vec<_> = operands
+                    .split_whitespace()
+                    .map(|o| parse_number(input, o))
+                    .try_collect()?;
+                if operands.is_empty() {
+                    return Err(ParseError::at(input, end_of(l), "at least one operand"));
+                }
+                Ok((value, operands))
+            })
+            .collect()
+    }
+
+    fn part1(equations: &Self::Input) -> Answer {
+        sum_bridge_calibrations_from_two_operations(equations)
+    }
+
+    fn part2(equations: &Self::Input) -> Option<Answer> {
+        Some(sum_bridge_calibrations_from_three_operations(equations))
+    }
+}
+
+/// An operator's symbol and how it combines two numbers, returning `None` if the result
+/// overflows, which can't match any test value.
+pub type Operator = (&'static str, fn(u64, u64) -> Option<u64>);
+
+pub fn sum_bridge_calibrations_from_operations(
+    equations: &[(u64, Vec<u64>)],
+    operator_set: &[Operator],
+) -> u64 {
+    equations
+        .iter()
+        .filter_map(|(value, operands)| {
+            for operators in (0..operands.len() - 1)
+                .map(|_| operator_set)
+                .multi_cartesian_product()
+            {
+                let result = operands
+                    .iter()
+                    .skip(1)
+                    .enumerate()
+                    .try_fold(operands[0], |lhs, (i, rhs)| operators[i].1(lhs, *rhs));
+
+                if result == Some(*value) {
+                    return Some(*value);
+                }
+            }
+
+            None::<u64>
+        })
+        .sum()
+}
+
+pub fn sum_bridge_calibrations_from_two_operations(equations: &[(u64, Vec<u64>)]) -> u64 {
+    sum_bridge_calibrations_from_operations(
+        equations,
+        &[("+", u64::checked_add), ("*", u64::checked_mul)],
+    )
+}
+
+pub fn sum_bridge_calibrations_from_three_operations(equations: &[(u64, Vec<u64>)]) -> u64 {
+    sum_bridge_calibrations_from_operations(
+        equations,
+        &[
+            ("+", u64::checked_add),
+            ("*", u64::checked_mul),
+            ("||", concatenate),
+        ],
+    )
+}
+
+/// Writes the digits of `r` after the digits of `l`, returning `None` if the result overflows.
+fn concatenate(l: u64, r: u64) -> Option<u64> {
+    let digits = r.checked_ilog10().map_or(1, |log| log + 1);
+    10u64.checked_pow(digits)?.checked_mul(l)?.checked_add(r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(Day7::solve(EXAMPLE).unwrap(), (3749, Some(11387)));
+    }
+
+    #[test]
+    fn overflowing_operators_do_not_match() {
+        assert_eq!(concatenate(12, 345), Some(12345));
+        assert_eq!(concatenate(7, 0), Some(70));
+        assert_eq!(concatenate(9999999999, 99999999999), None);
+
+        assert_eq!(
+            Day7::solve("1: 9999999999 99999999999\n").unwrap(),
+            (0, Some(0))
+        );
+        assert_eq!(
+            Day7::solve("18446744073709551615: 18446744073709551615 2\n").unwrap(),
+            (0, Some(0))
+        );
+        assert_eq!(Day7::solve("111: 1 1 1\n").unwrap(), (0, Some(111)));
+    }
+}
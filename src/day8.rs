@@ -0,0 +1,143 @@
+use crate::{
+    error::ParseError,
+    grid::{Grid, Position},
+    point::Point,
+    render::{Color, Render},
+    solution::{Answer, Solution},
+};
+use itertools::Itertools;
+use std::collections::HashMap;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::parse_chars(input)
+    }
+
+    fn part1(lab_map: &Self::Input) -> Answer {
+        sum_unique_antinode_locations(lab_map) as Answer
+    }
+
+    fn part2(lab_map: &Self::Input) -> Option<Answer> {
+        Some(sum_unique_antinode_locations_accounting_for_resonant_harmonics(lab_map) as Answer)
+    }
+
+    fn render(lab_map: &Self::Input, part: u8) -> Option<Render> {
+        let antinodes = match part {
+            1 => find_unique_antinode_locations(lab_map),
+            _ => find_unique_antinode_locations_accounting_for_resonant_harmonics(lab_map),
+        };
+
+        // antennae that are also antinodes keep their frequency, but are colored as antinodes
+        let (antennae, empty): (Vec<_>, Vec<_>) = antinodes
+            .into_iter()
+            .partition(|p| lab_map[*p].is_ascii_alphanumeric());
+        Some(
+            Render::new(lab_map, |c| *c)
+                .highlight(empty, Some('#'), Color::Red)
+                .highlight(antennae, None, Color::Red),
+        )
+    }
+}
+
+/// Reads the map, creating a hashmap of antenna frequencies (keys) and locations (values).
+fn find_antenna_locations(lab_map: &Grid<char>) -> HashMap<char, Vec<Point>> {
+    let mut antenna_locations: HashMap<char, Vec<Point>> = HashMap::new();
+    for (position, cell) in lab_map.iter() {
+        if cell.is_ascii_alphanumeric() {
+            antenna_locations
+                .entry(*cell)
+                .or_default()
+                .push(position.into());
+        }
+    }
+
+    antenna_locations
+}
+
+pub fn sum_unique_antinode_locations(lab_map: &Grid<char>) -> usize {
+    find_unique_antinode_locations(lab_map).len()
+}
+
+pub fn find_unique_antinode_locations(lab_map: &Grid<char>) -> Vec<Position> {
+    let mut antinodes: Vec<Position> = vec![];
+
+    // For each pair of antennae at a given freqency, calculate their two antinodes
+    for (_, locations) in find_antenna_locations(lab_map) {
+        for perm in locations.iter().combinations(2) {
+            let diff = *perm[0] - *perm[1];
+
+            // Save the antinodes if they are within the bounds of the map
+            antinodes.extend(lab_map.locate(*perm[0] + diff));
+            antinodes.extend(lab_map.locate(*perm[1] - diff));
+        }
+    }
+
+    antinodes.into_iter().unique().collect()
+}
+
+pub fn sum_unique_antinode_locations_accounting_for_resonant_harmonics(
+    lab_map: &Grid<char>,
+) -> usize {
+    find_unique_antinode_locations_accounting_for_resonant_harmonics(lab_map).len()
+}
+
+pub fn find_unique_antinode_locations_accounting_for_resonant_harmonics(
+    lab_map: &Grid<char>,
+) -> Vec<Position> {
+    let mut antinodes: Vec<Position> = vec![];
+
+    // For each pair of antennae at a given freqency, calculate their antinodes
+    for (_, locations) in find_antenna_locations(lab_map) {
+        for combo in locations.iter().combinations(2) {
+            // every grid position in line with the pair is an antinode, including the antennae
+            // themselves, so step between whole positions along the line
+            let step = (*combo[0] - *combo[1]).normalized();
+
+            // Search for antinodes in one direction, as long as they are within the bounds of
+            // the map
+            let mut antinode = *combo[0];
+            while let Some(position) = lab_map.locate(antinode) {
+                antinodes.push(position);
+                antinode += step;
+            }
+
+            // Search for antinodes in the other direction
+            let mut antinode = *combo[0] - step;
+            while let Some(position) = lab_map.locate(antinode) {
+                antinodes.push(position);
+                antinode -= step;
+            }
+        }
+    }
+
+    antinodes.into_iter().unique().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(Day8::solve(EXAMPLE).unwrap(), (14, Some(34)));
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{
+    error::{end_of, parse_digits, ParseError},
+    solution::{Answer, Solution},
+};
+use itertools::Itertools;
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<u64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut lines = input.lines();
+        let diskmap = lines.next().unwrap_or(input);
+        if let Some(extra) = lines.next() {
+            return Err(ParseError::at(input, extra, "a disk map on a single line"));
+        }
+
+        if diskmap.is_empty() {
+            return Err(ParseError::at(input, end_of(input), "a disk map"));
+        }
+
+        parse_digits(input, diskmap)
+            .map_ok(|d| d as u64)
+            .try_collect()
+    }
+
+    fn part1(diskmap: &Self::Input) -> Answer {
+        compute_filesystem_checksum_following_block_compaction(diskmap) as Answer
+    }
+
+    fn part2(diskmap: &Self::Input) -> Option<Answer> {
+        Some(compute_filesystem_checksum_following_file_compaction(diskmap) as Answer)
+    }
+}
+
+pub fn compute_filesystem_checksum_following_block_compaction(diskmap: &[u64]) -> usize {
+    let mut disk_model = Vec::new();
+    for (id, chunk) in diskmap.iter().copied().chunks(2).into_iter().enumerate() {
+        if let Some((num_file, num_free)) = chunk.collect_tuple() {
+            for _ in 0..num_file {
+                disk_model.push(id);
+            }
+            for _ in 0..num_free {
+                disk_model.push(usize::MAX);
+            }
+        } else {
+            for _ in 0..*diskmap.last().unwrap() {
+                disk_model.push(id);
+            }
+        }
+    }
+
+    let mut compacted_model = disk_model.clone();
+    // the first free block, and just past the last file block
+    let mut free_i: usize = 0;
+    let mut file_end: usize = compacted_model.len();
+    loop {
+        while free_i < compacted_model.len() && compacted_model[free_i] != usize::MAX {
+            free_i += 1;
+        }
+        while file_end > 0 && compacted_model[file_end - 1] == usize::MAX {
+            file_end -= 1;
+        }
+
+        // stop once every file block is before every free block
+        if free_i + 1 >= file_end {
+            break;
+        }
+
+        compacted_model.swap(free_i, file_end - 1);
+    }
+
+    compacted_model
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, id)| if id == usize::MAX { None } else { Some(i * id) })
+        .sum::<usize>()
+}
+
+pub fn compute_filesystem_checksum_following_file_compaction(diskmap: &[u64]) -> usize {
+    let mut disk_model = Vec::new();
+    for (id, chunk) in diskmap.iter().copied().chunks(2).into_iter().enumerate() {
+        if let Some((num_file, num_free)) = chunk.collect_tuple() {
+            disk_model.push((id, num_file));
+            disk_model.push((usize::MAX, num_free));
+        } else {
+            disk_model.push((id, *diskmap.last().unwrap()));
+        }
+    }
+
+    let mut compacted_model = disk_model.clone();
+    let Some(mut file_i) = compacted_model.len().checked_sub(1) else {
+        return 0;
+    };
+    loop {
+        let mut free_i: usize = 0;
+        let mut file = compacted_model[file_i];
+        while file.0 == usize::MAX {
+            file_i -= 1;
+            if file_i <= free_i {
+                break;
+            }
+            file = compacted_model[file_i];
+        }
+
+        let mut free = compacted_model[free_i];
+        while free.0 != usize::MAX || free.1 < file.1 {
+            free_i += 1;
+            if file_i <= free_i {
+                break;
+            }
+            free = compacted_model[free_i];
+        }
+
+        if free_i < file_i {
+            assert!(free.1 >= file.1);
+
+            compacted_model[free_i] = file;
+            compacted_model[file_i] = (usize::MAX, file.1);
+            if file.1 != free.1 {
+                file_i += 1;
+                free_i += 1;
+                compacted_model.insert(free_i, (usize::MAX, free.1 - file.1));
+            }
+        }
+
+        if file_i == 0 {
+            break;
+        }
+
+        file_i -= 1;
+    }
+
+    let mut model_i = 0;
+    compacted_model
+        .into_iter()
+        .filter_map(|region| {
+            if region.0 == usize::MAX {
+                model_i += region.1 as usize;
+                None
+            } else {
+                let mut sum = 0;
+                for _ in 0..region.1 {
+                    sum += model_i * region.0;
+                    model_i += 1;
+                }
+                Some(sum)
+            }
+        })
+        .sum::<usize>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            Day9::solve("2333133121414131402\n").unwrap(),
+            (1928, Some(2858))
+        );
+        assert_eq!(Day9::solve("12345\n").unwrap(), (60, Some(132)));
+    }
+
+    #[test]
+    fn short_disk_maps() {
+        assert_eq!(
+            Day9::parse("").unwrap_err().to_string(),
+            "1:1: expected a disk map, found end of input"
+        );
+        assert_eq!(Day9::solve("1").unwrap(), (0, Some(0)));
+        assert_eq!(Day9::solve("10").unwrap(), (0, Some(0)));
+        assert_eq!(Day9::solve("12").unwrap(), (0, Some(0)));
+        assert_eq!(Day9::solve("213").unwrap(), (9, Some(12)));
+        assert_eq!(
+            compute_filesystem_checksum_following_block_compaction(&[]),
+            0
+        );
+        assert_eq!(
+            compute_filesystem_checksum_following_file_compaction(&[]),
+            0
+        );
+    }
+}
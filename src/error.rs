@@ -0,0 +1,105 @@
+use std::{fmt, io, str::FromStr};
+
+/// Why a puzzle input could not be parsed, and where in the input the problem was found.
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column number, counted in characters.
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl ParseError {
+    /// Creates an error located at `token`, which must be a slice of `input` (as returned by
+    /// `lines`, `split` and friends). An empty token reports that the line or input ended early.
+    pub fn at(input: &str, token: &str, expected: impl Into<String>) -> Self {
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|offset| offset + token.len() <= input.len())
+            .expect("token is not a slice of the input");
+
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            expected: expected.into(),
+            found: if token.is_empty() && offset == input.len() {
+                "end of input".to_string()
+            } else if token.is_empty() {
+                "end of line".to_string()
+            } else {
+                format!("`{}`", token)
+            },
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: expected {}, found {}",
+            self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses `token`, a slice of `input`, as a number.
+pub fn parse_number<T: FromStr>(input: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::at(input, token, "a number"))
+}
+
+/// Parses each character of `line`, a slice of `input`, as a decimal digit.
+pub fn parse_digits<'a>(
+    input: &'a str,
+    line: &'a str,
+) -> impl Iterator<Item = Result<u32, ParseError>> + 'a {
+    line.char_indices().map(move |(i, c)| {
+        c.to_digit(10)
+            .ok_or_else(|| ParseError::at(input, &line[i..i + c.len_utf8()], "a digit"))
+    })
+}
+
+/// Returns the empty slice at the end of `line`, for reporting that something is missing there.
+pub fn end_of(line: &str) -> &str {
+    &line[line.len()..]
+}
+
+/// Everything that can go wrong while loading and solving a puzzle.
+#[derive(Debug)]
+pub enum Error {
+    /// The input named `name` could not be read.
+    Io { name: String, source: io::Error },
+    /// The input named `name` was read but is malformed.
+    Parse { name: String, source: ParseError },
+    /// Results could not be written out.
+    Output(io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { name, source } => write!(f, "cannot read {}: {}", name, source),
+            Error::Parse { name, source } => write!(f, "{}:{}", name, source),
+            Error::Output(source) => write!(f, "cannot write output: {}", source),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Parse { source, .. } => Some(source),
+            Error::Output(source) => Some(source),
+        }
+    }
+}
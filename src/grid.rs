@@ -0,0 +1,247 @@
+use crate::{
+    direction::{Direction, Direction8},
+    error::{end_of, ParseError},
+    point::{Point, Vector},
+};
+use std::ops::{Index, IndexMut};
+
+/// A `(row, column)` position in a grid.
+pub type Position = (usize, usize);
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl Grid<char> {
+    /// Parses each line of `input` as a row of characters.
+    pub fn parse_chars(input: &str) -> Result<Self, ParseError> {
+        Grid::parse(input, Some, "a character")
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parses each line of `input` as a row, converting each character to a cell with `cell`.
+    /// Fails where `cell` returns `None`, or where a row is longer or shorter than the first.
+    pub fn parse(
+        input: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+        expected: &str,
+    ) -> Result<Self, ParseError> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+
+        for line in input.lines() {
+            let mut row_width = 0;
+            for (j, c) in line.char_indices() {
+                let token = &line[j..j + c.len_utf8()];
+                if width == Some(row_width) {
+                    return Err(ParseError::at(input, token, "end of line"));
+                }
+
+                cells.push(cell(c).ok_or_else(|| ParseError::at(input, token, expected))?);
+                row_width += 1;
+            }
+
+            match width {
+                Some(width) if row_width < width => {
+                    return Err(ParseError::at(input, end_of(line), expected))
+                }
+                Some(_) => (),
+                None => width = Some(row_width),
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (i, j): Position) -> bool {
+        i < self.height && j < self.width
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[position.0 * self.width + position.1])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.contains(position)
+            .then(|| &mut self.cells[position.0 * self.width + position.1])
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.cells[i * self.width..(i + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` rejects a chunk size of zero, so an empty grid has no rows to split
+        (0..self.height).map(|i| self.row(i))
+    }
+
+    pub fn column(&self, j: usize) -> impl Iterator<Item = &T> {
+        assert!(j < self.width, "column {} is out of bounds", j);
+        self.cells.iter().skip(j).step_by(self.width)
+    }
+
+    /// Every position in the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |i| (0..width).map(move |j| (i, j)))
+    }
+
+    /// Every cell in the grid with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// Converts `point` to the position of a cell in the grid, or returns `None` if it's outside
+    /// the grid.
+    pub fn locate(&self, point: Point) -> Option<Position> {
+        point.to_position().filter(|&p| self.contains(p))
+    }
+
+    /// Returns the position one step of `by` away from `position`, if it's in the grid.
+    pub fn step(&self, position: Position, by: impl Into<Vector>) -> Option<Position> {
+        self.locate(Point::from(position) + by.into())
+    }
+
+    /// Returns the position one step of `by` away from `position`, wrapping around to the
+    /// opposite edge of the grid if it would leave it.
+    pub fn wrapping_step(&self, position: Position, by: impl Into<Vector>) -> Position {
+        let point = Point::from(position) + by.into();
+        (
+            point.row.rem_euclid(self.height as i64) as usize,
+            point.column.rem_euclid(self.width as i64) as usize,
+        )
+    }
+
+    /// The positions of the orthogonal neighbors of `position` that are in the grid.
+    pub fn neighbors4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| self.step(position, d))
+    }
+
+    /// The positions of all eight neighbors of `position` that are in the grid.
+    pub fn neighbors8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction8::ALL
+            .into_iter()
+            .filter_map(move |d| self.step(position, d))
+    }
+
+    /// The position of the first cell, row by row, that equals `value`.
+    pub fn find(&self, value: &T) -> Option<Position>
+    where
+        T: PartialEq,
+    {
+        self.iter().find(|(_, cell)| *cell == value).map(|(p, _)| p)
+    }
+
+    /// The positions of every cell that equals `value`, row by row.
+    pub fn positions_of<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Position> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(p, _)| p)
+    }
+
+    /// Returns a grid of the same shape with `f` applied to each cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("{:?} is out of bounds", position))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{:?} is out of bounds", position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rows_and_columns() {
+        let grid = Grid::parse_chars("abc\ndef\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+        assert_eq!(grid.get((1, 2)), Some(&'f'));
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.find(&'e'), Some((1, 1)));
+    }
+
+    #[test]
+    fn parse_rejects_ragged_rows_and_bad_cells() {
+        let digit = |c: char| c.to_digit(10);
+        assert_eq!(
+            Grid::parse("123\n12\n", digit, "a digit")
+                .unwrap_err()
+                .to_string(),
+            "2:3: expected a digit, found end of line"
+        );
+        assert_eq!(
+            Grid::parse("123\n1234\n", digit, "a digit")
+                .unwrap_err()
+                .to_string(),
+            "2:4: expected end of line, found `4`"
+        );
+        assert_eq!(
+            Grid::parse("123\n1x3\n", digit, "a digit")
+                .unwrap_err()
+                .to_string(),
+            "2:2: expected a digit, found `x`"
+        );
+    }
+
+    #[test]
+    fn neighbors_stay_in_bounds() {
+        let grid = Grid::parse_chars("abc\ndef\nghi\n").unwrap();
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0)]
+        );
+        assert_eq!(grid.neighbors4((1, 1)).count(), 4);
+        assert_eq!(
+            grid.neighbors8((2, 2)).collect::<Vec<_>>(),
+            [(1, 2), (2, 1), (1, 1)]
+        );
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+        assert_eq!(grid.wrapping_step((0, 2), Direction8::UpRight), (2, 0));
+        assert_eq!(grid.wrapping_step((1, 1), Direction8::Down), (2, 1));
+    }
+}
@@ -0,0 +1,209 @@
+use crate::grid::{Grid, Position};
+use std::{fs, io, path::Path};
+
+/// A color as red, green and blue components.
+pub type Rgb = [u8; 3];
+
+/// An RGB image that can be saved as PPM or PNG without any image libraries.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    /// Draws `grid` with one pixel per cell, colored by `color`.
+    pub fn from_grid<T>(grid: &Grid<T>, mut color: impl FnMut(Position, &T) -> Rgb) -> Image {
+        Image {
+            width: grid.width(),
+            height: grid.height(),
+            pixels: grid.iter().map(|(p, cell)| color(p, cell)).collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the image with each pixel enlarged to a `factor` by `factor` square.
+    pub fn scaled(&self, factor: usize) -> Image {
+        let mut pixels = Vec::with_capacity(self.pixels.len() * factor * factor);
+        for row in self.pixels.chunks(self.width.max(1)) {
+            for _ in 0..factor {
+                for pixel in row {
+                    pixels.extend(std::iter::repeat_n(*pixel, factor));
+                }
+            }
+        }
+
+        Image {
+            width: self.width * factor,
+            height: self.height * factor,
+            pixels,
+        }
+    }
+
+    /// Encodes the image as a binary PPM (P6) file.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        bytes.extend(self.pixels.iter().flatten());
+        bytes
+    }
+
+    /// Encodes the image as a PNG file. The image data is stored uncompressed, which keeps the
+    /// encoder small at the cost of larger files.
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut header = Vec::with_capacity(13);
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        // 8 bits per channel, RGB, default compression, filtering and no interlacing
+        header.extend([8, 2, 0, 0, 0]);
+
+        // every row starts with its filter type, which is always "none" here
+        let mut data = Vec::with_capacity(self.height * (1 + 3 * self.width));
+        for row in self.pixels.chunks(self.width.max(1)).take(self.height) {
+            data.push(0);
+            data.extend(row.iter().flatten());
+        }
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        write_png_chunk(&mut png, b"IHDR", &header);
+        write_png_chunk(&mut png, b"IDAT", &zlib_stored(&data));
+        write_png_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+
+    /// Saves the image to `path`, as PNG if its extension is `png` and as PPM otherwise.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let bytes = match path.extension() {
+            Some(extension) if extension.eq_ignore_ascii_case("png") => self.to_png(),
+            _ => self.to_ppm(),
+        };
+        fs::write(path, bytes)
+    }
+}
+
+fn write_png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+/// Wraps `data` in a zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut stream = vec![0x78, 0x01];
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        stream.extend([1, 0, 0, 0xff, 0xff]);
+    }
+
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        stream.push(last as u8);
+        stream.extend((block.len() as u16).to_le_bytes());
+        stream.extend((!(block.len() as u16)).to_le_bytes());
+        stream.extend(block);
+    }
+
+    stream.extend(adler32(data).to_be_bytes());
+    stream
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0, |mut crc: u32, byte| {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+        crc
+    })
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (a, b) = bytes.iter().fold((1u32, 0u32), |(a, b), byte| {
+        let a = (a + *byte as u32) % 65521;
+        (a, (b + a) % 65521)
+    });
+    (b << 16) | a
+}
+
+/// Colors `value` on a scale from black through red and yellow to white, with `max` the hottest.
+pub fn heat(value: usize, max: usize) -> Rgb {
+    let t = (value.min(max) * 765 / max.max(1)) as u16;
+    let channel = |start: u16| t.saturating_sub(start).min(255) as u8;
+    [channel(0), channel(255), channel(510)]
+}
+
+/// A distinct color for each id, spreading consecutive ids around the color wheel.
+pub fn palette(id: usize) -> Rgb {
+    // stepping by the golden angle keeps neighboring ids far apart in hue
+    let hue = (id as f64 * 137.508) % 360.0;
+    let sector = hue / 60.0;
+    let x = (1.0 - (sector % 2.0 - 1.0).abs()) * 200.0;
+    let (r, g, b) = match sector as u8 {
+        0 => (200.0, x, 0.0),
+        1 => (x, 200.0, 0.0),
+        2 => (0.0, 200.0, x),
+        3 => (0.0, x, 200.0),
+        4 => (x, 0.0, 200.0),
+        _ => (200.0, 0.0, x),
+    };
+    [r as u8 + 40, g as u8 + 40, b as u8 + 40]
+}
+
+/// Shades `color` from near black at 0 to full brightness at `max`.
+pub fn shade(color: Rgb, value: usize, max: usize) -> Rgb {
+    let brightness = 40 + value.min(max) * 215 / max.max(1);
+    color.map(|c| (c as usize * brightness / 255) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image() -> Image {
+        let grid = Grid::parse_chars("ab\ncd\n").unwrap();
+        Image::from_grid(&grid, |_, c| [*c as u8, 0, 255])
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn ppm_and_png_encodings() {
+        let image = image().scaled(2);
+        assert_eq!((image.width(), image.height()), (4, 4));
+
+        let ppm = image.to_ppm();
+        assert!(ppm.starts_with(b"P6\n4 4\n255\n"));
+        assert_eq!(ppm.len(), 11 + 4 * 4 * 3);
+        assert_eq!(ppm[11..17], [b'a', 0, 255, b'a', 0, 255]);
+
+        let png = image.to_png();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x04\0\0\0\x04"));
+        assert!(png.ends_with(b"\0\0\0\0IEND\xae\x42\x60\x82"));
+    }
+
+    #[test]
+    fn heat_runs_from_black_to_white() {
+        assert_eq!(heat(0, 4), [0, 0, 0]);
+        assert_eq!(heat(2, 6), [255, 0, 0]);
+        assert_eq!(heat(4, 4), [255, 255, 255]);
+        assert_eq!(heat(9, 4), [255, 255, 255]);
+    }
+}
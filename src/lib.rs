@@ -0,0 +1,67 @@
+pub mod answers;
+pub mod bench;
+pub mod columns;
+pub mod direction;
+pub mod error;
+pub mod grid;
+pub mod image;
+pub mod output;
+pub mod point;
+pub mod render;
+pub mod search;
+pub mod solution;
+pub mod source;
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+use day1::Day1;
+use day10::Day10;
+use day11::Day11;
+use day12::Day12;
+use day2::Day2;
+use day3::Day3;
+use day4::Day4;
+use day5::Day5;
+use day6::Day6;
+use day7::Day7;
+use day8::Day8;
+use day9::Day9;
+use solution::Puzzle;
+
+/// Every solved part of every day, in order.
+pub const PUZZLES: &[Puzzle] = &[
+    Puzzle::part1::<Day1>(1),
+    Puzzle::part2::<Day1>(1),
+    Puzzle::part1::<Day2>(2),
+    Puzzle::part2::<Day2>(2),
+    Puzzle::part1::<Day3>(3).streamed(day3::solve_reader),
+    Puzzle::part2::<Day3>(3).streamed(day3::solve_reader),
+    Puzzle::part1::<Day4>(4),
+    Puzzle::part2::<Day4>(4),
+    Puzzle::part1::<Day5>(5),
+    Puzzle::part2::<Day5>(5),
+    Puzzle::part1::<Day6>(6),
+    Puzzle::part2::<Day6>(6).slow(),
+    Puzzle::part1::<Day7>(7),
+    Puzzle::part2::<Day7>(7).slow(),
+    Puzzle::part1::<Day8>(8).slow(),
+    Puzzle::part2::<Day8>(8).slow(),
+    Puzzle::part1::<Day9>(9),
+    Puzzle::part2::<Day9>(9),
+    Puzzle::part1::<Day10>(10),
+    Puzzle::part2::<Day10>(10),
+    Puzzle::part1::<Day11>(11),
+    Puzzle::part2::<Day11>(11),
+    Puzzle::part1::<Day12>(12),
+];
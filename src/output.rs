@@ -0,0 +1,218 @@
+use std::{
+    io::{self, Write},
+    str::FromStr,
+};
+
+/// How results are printed.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Format {
+    /// Sentences for people to read; written by the caller rather than a [`RecordWriter`].
+    #[default]
+    Text,
+    /// A JSON array with one object per record.
+    Json,
+    /// Comma-separated values with a header row.
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!(
+                "invalid format '{}', expected text, json or csv",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Number(u128),
+    Text(String),
+    Null,
+}
+
+impl From<u8> for Value {
+    fn from(n: u8) -> Self {
+        Value::Number(n.into())
+    }
+}
+
+impl From<u64> for Value {
+    fn from(n: u64) -> Self {
+        Value::Number(n.into())
+    }
+}
+
+impl From<u128> for Value {
+    fn from(n: u128) -> Self {
+        Value::Number(n)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::Text(s.to_string())
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Value::Null, Into::into)
+    }
+}
+
+/// One result, as named values in a fixed order. Every record given to a [`RecordWriter`] should
+/// have the same names in the same order.
+#[derive(Debug, Default)]
+pub struct Record(Vec<(&'static str, Value)>);
+
+impl Record {
+    pub fn new() -> Self {
+        Record(Vec::new())
+    }
+
+    pub fn with(mut self, name: &'static str, value: impl Into<Value>) -> Self {
+        self.0.push((name, value.into()));
+        self
+    }
+}
+
+/// Writes records in a machine-readable format. Writing in [`Format::Text`] does nothing.
+pub struct RecordWriter<W: Write> {
+    format: Format,
+    out: W,
+    written: usize,
+}
+
+impl<W: Write> RecordWriter<W> {
+    pub fn new(format: Format, out: W) -> Self {
+        RecordWriter {
+            format,
+            out,
+            written: 0,
+        }
+    }
+
+    pub fn write(&mut self, record: &Record) -> io::Result<()> {
+        match self.format {
+            Format::Text => return Ok(()),
+            Format::Json => {
+                let fields = record
+                    .0
+                    .iter()
+                    .map(|(name, value)| format!("{}:{}", json_string(name), json_value(value)))
+                    .collect::<Vec<_>>();
+                // the closing bracket, or the comma before the next record, ends the line
+                let separator = if self.written == 0 { "[" } else { "," };
+                write!(self.out, "{}\n  {{{}}}", separator, fields.join(","))?;
+            }
+            Format::Csv => {
+                if self.written == 0 {
+                    let header = record.0.iter().map(|(name, _)| *name);
+                    writeln!(self.out, "{}", header.collect::<Vec<_>>().join(","))?;
+                }
+
+                let fields = record.0.iter().map(|(_, value)| csv_value(value));
+                writeln!(self.out, "{}", fields.collect::<Vec<_>>().join(","))?;
+            }
+        }
+
+        self.written += 1;
+        self.out.flush()
+    }
+
+    /// Completes the output once every record has been written.
+    pub fn finish(mut self) -> io::Result<()> {
+        match self.format {
+            Format::Json if self.written == 0 => writeln!(self.out, "[]")?,
+            Format::Json => writeln!(self.out, "\n]")?,
+            Format::Text | Format::Csv => (),
+        }
+
+        self.out.flush()
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => escaped += "\\\"",
+            '\\' => escaped += "\\\\",
+            '\n' => escaped += "\\n",
+            c if c.is_control() => escaped += &format!("\\u{:04x}", c as u32),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn json_value(value: &Value) -> String {
+    match value {
+        Value::Number(n) => n.to_string(),
+        Value::Text(s) => json_string(s),
+        Value::Null => "null".to_string(),
+    }
+}
+
+fn csv_value(value: &Value) -> String {
+    match value {
+        Value::Number(n) => n.to_string(),
+        Value::Text(s) if s.contains([',', '"', '\n']) => format!("\"{}\"", s.replace('"', "\"\"")),
+        Value::Text(s) => s.clone(),
+        Value::Null => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_all(format: Format, records: &[Record]) -> String {
+        let mut out = Vec::new();
+        let mut writer = RecordWriter::new(format, &mut out);
+        for record in records {
+            writer.write(record).unwrap();
+        }
+        writer.finish().unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record::new()
+                .with("day", 1u8)
+                .with("answer", Some(11u64))
+                .with("status", "solved"),
+            Record::new()
+                .with("day", 12u8)
+                .with("answer", None::<u64>)
+                .with("status", "say \"hi\", ok"),
+        ]
+    }
+
+    #[test]
+    fn json_is_an_array_of_objects() {
+        assert_eq!(
+            write_all(Format::Json, &records()),
+            "[\n  {\"day\":1,\"answer\":11,\"status\":\"solved\"},\n  {\"day\":12,\"answer\":null,\"status\":\"say \\\"hi\\\", ok\"}\n]\n"
+        );
+        assert_eq!(write_all(Format::Json, &[]), "[]\n");
+    }
+
+    #[test]
+    fn csv_has_a_header_and_quotes_where_needed() {
+        assert_eq!(
+            write_all(Format::Csv, &records()),
+            "day,answer,status\n1,11,solved\n12,,\"say \"\"hi\"\", ok\"\n"
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{
+    direction::{Direction, Direction8},
+    grid::Position,
+};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A point on an unbounded grid, which unlike a [`Position`] can lie above or to the left of the
+/// origin.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point {
+    pub row: i64,
+    pub column: i64,
+}
+
+/// The difference between two points.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Vector {
+    pub rows: i64,
+    pub columns: i64,
+}
+
+impl Point {
+    pub const fn new(row: i64, column: i64) -> Point {
+        Point { row, column }
+    }
+
+    pub fn manhattan_distance(self, other: Point) -> u64 {
+        (other - self).manhattan_length()
+    }
+
+    /// Converts the point to grid indices, or returns `None` if either coordinate is negative.
+    pub fn to_position(self) -> Option<Position> {
+        Some((self.row.try_into().ok()?, self.column.try_into().ok()?))
+    }
+}
+
+impl From<Position> for Point {
+    fn from((i, j): Position) -> Self {
+        Point::new(
+            i.try_into().expect("row is too large for a point"),
+            j.try_into().expect("column is too large for a point"),
+        )
+    }
+}
+
+impl Vector {
+    pub const fn new(rows: i64, columns: i64) -> Vector {
+        Vector { rows, columns }
+    }
+
+    pub fn manhattan_length(self) -> u64 {
+        self.rows.unsigned_abs() + self.columns.unsigned_abs()
+    }
+
+    /// Returns the shortest vector in the same direction with whole-number components, by
+    /// dividing both components by their greatest common divisor. The zero vector stays zero.
+    pub fn normalized(self) -> Vector {
+        let divisor = gcd(self.rows.unsigned_abs(), self.columns.unsigned_abs()).max(1) as i64;
+        Vector::new(self.rows / divisor, self.columns / divisor)
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl From<(isize, isize)> for Vector {
+    fn from((rows, columns): (isize, isize)) -> Self {
+        Vector::new(rows as i64, columns as i64)
+    }
+}
+
+impl From<Direction> for Vector {
+    fn from(direction: Direction) -> Self {
+        direction.delta().into()
+    }
+}
+
+impl From<Direction8> for Vector {
+    fn from(direction: Direction8) -> Self {
+        direction.delta().into()
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, v: Vector) -> Point {
+        Point::new(self.row + v.rows, self.column + v.columns)
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, v: Vector) {
+        *self = *self + v;
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, v: Vector) -> Point {
+        self + -v
+    }
+}
+
+impl SubAssign<Vector> for Point {
+    fn sub_assign(&mut self, v: Vector) {
+        *self = *self - v;
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, other: Point) -> Vector {
+        Vector::new(self.row - other.row, self.column - other.column)
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Vector) -> Vector {
+        Vector::new(self.rows + other.rows, self.columns + other.columns)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, other: Vector) -> Vector {
+        self + -other
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.rows, -self.columns)
+    }
+}
+
+impl Mul<i64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, factor: i64) -> Vector {
+        Vector::new(self.rows * factor, self.columns * factor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let (a, b) = (Point::new(3, 4), Point::new(5, 8));
+        assert_eq!(b - a, Vector::new(2, 4));
+        assert_eq!(a + (b - a) * 2, Point::new(7, 12));
+        assert_eq!(a - (b - a), Point::new(1, 0));
+        assert_eq!(a.manhattan_distance(b), 6);
+        assert_eq!(b.manhattan_distance(a), 6);
+        assert_eq!(Point::from((2, 1)) + Direction::Up.into(), Point::new(1, 1));
+    }
+
+    #[test]
+    fn normalized_divides_by_the_gcd() {
+        assert_eq!(Vector::new(-4, 6).normalized(), Vector::new(-2, 3));
+        assert_eq!(Vector::new(0, -5).normalized(), Vector::new(0, -1));
+        assert_eq!(Vector::new(3, 7).normalized(), Vector::new(3, 7));
+        assert_eq!(Vector::new(0, 0).normalized(), Vector::new(0, 0));
+    }
+
+    #[test]
+    fn to_position_is_checked() {
+        assert_eq!(Point::new(2, 3).to_position(), Some((2, 3)));
+        assert_eq!(Point::new(-1, 3).to_position(), None);
+        assert_eq!(Point::new(2, -3).to_position(), None);
+    }
+}
@@ -0,0 +1,203 @@
+use crate::{
+    direction::Direction,
+    grid::{Grid, Position},
+};
+use std::{collections::BTreeMap, fmt};
+
+/// Terminal colors for overlays, drawn with ANSI escape codes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Color {
+    pub const ALL: [Color; 6] = [
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+    ];
+
+    fn ansi_code(self) -> u8 {
+        match self {
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Cell {
+    symbol: char,
+    color: Option<Color>,
+}
+
+/// A grid drawn as text, one character per cell, with overlays drawn on top of it. Each overlay
+/// replaces the symbol and color of the cells it covers.
+#[derive(Clone, Debug)]
+pub struct Render {
+    cells: Grid<Cell>,
+}
+
+impl Render {
+    /// Starts a drawing of `grid`, showing each cell as `symbol` returns.
+    pub fn new<T>(grid: &Grid<T>, mut symbol: impl FnMut(&T) -> char) -> Render {
+        Render {
+            cells: grid.map(|cell| Cell {
+                symbol: symbol(cell),
+                color: None,
+            }),
+        }
+    }
+
+    /// Draws `positions` in `color`, replacing their symbols with `symbol` if one is given.
+    pub fn highlight(
+        mut self,
+        positions: impl IntoIterator<Item = Position>,
+        symbol: Option<char>,
+        color: Color,
+    ) -> Render {
+        for position in positions {
+            let cell = &mut self.cells[position];
+            cell.symbol = symbol.unwrap_or(cell.symbol);
+            cell.color = Some(color);
+        }
+        self
+    }
+
+    /// Draws a count in each of the given cells: a digit, or `+` for ten or more.
+    pub fn counts(
+        mut self,
+        counts: impl IntoIterator<Item = (Position, usize)>,
+        color: Color,
+    ) -> Render {
+        for (position, count) in counts {
+            self.cells[position] = Cell {
+                symbol: match count {
+                    0..=9 => char::from_digit(count as u32, 10).unwrap(),
+                    _ => '+',
+                },
+                color: Some(color),
+            };
+        }
+        self
+    }
+
+    /// Draws a path as arrows in the direction of travel. Cells that the path crosses in more
+    /// than one direction show how many directions it crossed them in.
+    pub fn path(
+        self,
+        path: impl IntoIterator<Item = (Position, Direction)>,
+        color: Color,
+    ) -> Render {
+        let mut directions: BTreeMap<Position, Vec<Direction>> = BTreeMap::new();
+        for (position, direction) in path {
+            let crossed = directions.entry(position).or_default();
+            if !crossed.contains(&direction) {
+                crossed.push(direction);
+            }
+        }
+
+        let (arrows, counts): (Vec<_>, Vec<_>) = directions
+            .into_iter()
+            .partition(|(_, crossed)| crossed.len() == 1);
+        let mut render = self.counts(
+            counts.into_iter().map(|(p, crossed)| (p, crossed.len())),
+            color,
+        );
+        for (position, crossed) in arrows {
+            render.cells[position] = Cell {
+                symbol: crossed[0].to_char(),
+                color: Some(color),
+            };
+        }
+        render
+    }
+
+    /// Colors each cell by the id of the region it belongs to, cycling through the palette.
+    pub fn regions(mut self, regions: &Grid<usize>) -> Render {
+        for (position, region) in regions.iter() {
+            self.cells[position].color = Some(Color::ALL[region % Color::ALL.len()]);
+        }
+        self
+    }
+
+    /// Returns the drawing as lines of text, with ANSI colors if `color` is set.
+    pub fn draw(&self, color: bool) -> String {
+        let mut text = String::new();
+        for row in self.cells.rows() {
+            let mut current = None;
+            for cell in row {
+                let cell_color = if color { cell.color } else { None };
+                if cell_color != current {
+                    match cell_color {
+                        Some(c) => text += &format!("\x1b[{}m", c.ansi_code()),
+                        None => text += "\x1b[0m",
+                    }
+                    current = cell_color;
+                }
+                text.push(cell.symbol);
+            }
+
+            if current.is_some() {
+                text += "\x1b[0m";
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl fmt::Display for Render {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.draw(false))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlays_replace_symbols() {
+        let grid = Grid::parse_chars("...\n.#.\n...\n").unwrap();
+        let path = [
+            ((2, 0), Direction::Up),
+            ((1, 0), Direction::Up),
+            ((0, 0), Direction::Right),
+            ((0, 0), Direction::Up),
+            ((0, 1), Direction::Right),
+        ];
+        let render = Render::new(&grid, |c| *c)
+            .highlight([(2, 2)], Some('O'), Color::Red)
+            .path(path, Color::Green);
+        assert_eq!(render.to_string(), "2>.\n^#.\n^.O\n");
+    }
+
+    #[test]
+    fn colors_are_drawn_in_runs() {
+        let grid = Grid::parse_chars("ab\ncd\n").unwrap();
+        let regions = Grid::parse(
+            "01\n11\n",
+            |c| c.to_digit(10).map(|d| d as usize),
+            "a digit",
+        )
+        .unwrap();
+        let render = Render::new(&grid, |c| *c).regions(&regions);
+        assert_eq!(
+            render.draw(true),
+            "\x1b[31ma\x1b[32mb\x1b[0m\n\x1b[32mcd\x1b[0m\n"
+        );
+    }
+}
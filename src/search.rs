@@ -0,0 +1,182 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+/// Visits every node reachable from `start`, breadth first. Returns the nodes in the order they
+/// were visited, nearest first.
+pub fn bfs<N, I>(start: N, neighbors: impl FnMut(N) -> I) -> Vec<N>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    bfs_from([start], neighbors, &mut HashSet::new())
+}
+
+/// Visits every node reachable from `start`, depth first. Returns the nodes in the order they
+/// were visited.
+pub fn dfs<N, I>(start: N, mut neighbors: impl FnMut(N) -> I) -> Vec<N>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut visited = Vec::new();
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        if seen.insert(node) {
+            visited.push(node);
+            stack.extend(neighbors(node));
+        }
+    }
+
+    visited
+}
+
+/// Returns every node reachable from any of `starts`, including the starts themselves.
+pub fn reachable<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbors: impl FnMut(N) -> I,
+) -> HashSet<N>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    bfs_from(starts, neighbors, &mut seen);
+    seen
+}
+
+/// Splits `nodes` into groups that are connected to each other, in the order that each group's
+/// first node appears in `nodes`. `neighbors` should be symmetric: if `b` is a neighbor of `a`
+/// then `a` is a neighbor of `b`.
+pub fn connected_components<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(N) -> I,
+) -> Vec<Vec<N>>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut components = Vec::new();
+    for node in nodes {
+        if !seen.contains(&node) {
+            components.push(bfs_from([node], &mut neighbors, &mut seen));
+        }
+    }
+
+    components
+}
+
+/// Counts the distinct paths from `start` to a node for which `is_goal` returns true, without
+/// following paths beyond a goal. The graph reachable from `start` must not contain cycles.
+pub fn count_paths<N, I>(
+    start: N,
+    mut is_goal: impl FnMut(N) -> bool,
+    mut neighbors: impl FnMut(N) -> I,
+) -> usize
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    // each node is counted once all of its neighbors have been, which is after the second time
+    // it comes off the stack
+    let mut counts: HashMap<N, usize> = HashMap::new();
+    let mut stack = vec![(start, false)];
+    while let Some((node, expanded)) = stack.pop() {
+        if counts.contains_key(&node) {
+            continue;
+        }
+
+        if is_goal(node) {
+            counts.insert(node, 1);
+        } else if expanded {
+            let count = neighbors(node).into_iter().map(|n| counts[&n]).sum();
+            counts.insert(node, count);
+        } else {
+            stack.push((node, true));
+            stack.extend(
+                neighbors(node)
+                    .into_iter()
+                    .filter(|n| !counts.contains_key(n))
+                    .map(|n| (n, false)),
+            );
+        }
+    }
+
+    counts[&start]
+}
+
+/// Breadth-first search from `starts`, skipping nodes already in `seen` and adding the nodes it
+/// visits to it.
+fn bfs_from<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(N) -> I,
+    seen: &mut HashSet<N>,
+) -> Vec<N>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut queue: VecDeque<N> = starts.into_iter().filter(|n| seen.insert(*n)).collect();
+    let mut visited = Vec::new();
+    while let Some(node) = queue.pop_front() {
+        visited.push(node);
+        for neighbor in neighbors(node) {
+            if seen.insert(neighbor) {
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    visited
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 1 -> 2 -> 4 -> 5
+    //   \-> 3 -/
+    // 6 -> 7
+    fn neighbors(node: u8) -> Vec<u8> {
+        match node {
+            1 => vec![2, 3],
+            2 | 3 => vec![4],
+            4 => vec![5],
+            6 => vec![7],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn traversals_visit_each_reachable_node_once() {
+        assert_eq!(bfs(1, neighbors), [1, 2, 3, 4, 5]);
+        assert_eq!(dfs(1, neighbors), [1, 3, 4, 5, 2]);
+        assert_eq!(reachable([3, 6], neighbors), HashSet::from([3, 4, 5, 6, 7]));
+    }
+
+    #[test]
+    fn connected_components_in_order_of_first_node() {
+        let undirected = |node: u8| match node {
+            1 => vec![2],
+            2 => vec![1, 3],
+            3 => vec![2],
+            4 => vec![5],
+            5 => vec![4],
+            _ => vec![],
+        };
+        assert_eq!(
+            connected_components([5, 1, 6, 2, 3, 4], undirected),
+            [vec![5, 4], vec![1, 2, 3], vec![6]]
+        );
+    }
+
+    #[test]
+    fn count_paths_through_shared_nodes() {
+        assert_eq!(count_paths(1, |n| n == 5, neighbors), 2);
+        assert_eq!(count_paths(1, |n| n == 4, neighbors), 2);
+        assert_eq!(count_paths(1, |n| n == 7, neighbors), 0);
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{error::ParseError, image::Image, render::Render};
+use std::{
+    any::Any,
+    io::{self, Read},
+};
+
+/// The answer to one part of a day's puzzle.
+pub type Answer = u64;
+
+/// A day's puzzle: how to parse its input, and how to solve each part from the parsed input.
+pub trait Solution {
+    type Input: 'static;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input) -> Answer;
+
+    /// Returns `None` until the second part of the puzzle has been solved.
+    fn part2(_input: &Self::Input) -> Option<Answer> {
+        None
+    }
+
+    /// Draws the input with the working behind the answer to `part` laid over it, or returns
+    /// `None` if there's nothing worth drawing.
+    fn render(_input: &Self::Input, _part: u8) -> Option<Render> {
+        None
+    }
+
+    /// Draws the input and the working behind the answer to `part` as an image, or returns `None`
+    /// if there's nothing worth drawing.
+    fn image(_input: &Self::Input, _part: u8) -> Option<Image> {
+        None
+    }
+
+    /// Explains the answer to `part` item by item, one per line, or returns `None` if there's
+    /// nothing worth explaining.
+    fn explain(_input: &Self::Input, _part: u8) -> Option<String> {
+        None
+    }
+
+    /// Parses `input` and solves both parts of the puzzle from it.
+    fn solve(input: &str) -> Result<(Answer, Option<Answer>), ParseError> {
+        let input = Self::parse(input)?;
+        Ok((Self::part1(&input), Self::part2(&input)))
+    }
+}
+
+/// Solves both parts of a day's puzzle from input read a chunk at a time, like
+/// [`Solution::solve`].
+pub type SolveReader = fn(&mut dyn Read) -> io::Result<(Answer, Option<Answer>)>;
+
+/// A day's parsed input, as produced by [`Puzzle::parse`].
+pub struct ParsedInput(Box<dyn Any>);
+
+/// One part of a day's puzzle, as listed in the registry.
+pub struct Puzzle {
+    pub day: u8,
+    pub part: u8,
+    pub slow: bool,
+    parse: fn(&str) -> Result<ParsedInput, ParseError>,
+    solve: fn(&ParsedInput) -> Option<Answer>,
+    render: fn(&ParsedInput, u8) -> Option<Render>,
+    image: fn(&ParsedInput, u8) -> Option<Image>,
+    explain: fn(&ParsedInput, u8) -> Option<String>,
+    solve_reader: Option<SolveReader>,
+}
+
+impl Puzzle {
+    pub const fn part1<S: Solution>(day: u8) -> Self {
+        Puzzle {
+            day,
+            part: 1,
+            slow: false,
+            parse: parse::<S>,
+            solve: solve_part1::<S>,
+            render: render::<S>,
+            image: image::<S>,
+            explain: explain::<S>,
+            solve_reader: None,
+        }
+    }
+
+    pub const fn part2<S: Solution>(day: u8) -> Self {
+        Puzzle {
+            day,
+            part: 2,
+            slow: false,
+            parse: parse::<S>,
+            solve: solve_part2::<S>,
+            render: render::<S>,
+            image: image::<S>,
+            explain: explain::<S>,
+            solve_reader: None,
+        }
+    }
+
+    pub const fn slow(self) -> Self {
+        Puzzle { slow: true, ..self }
+    }
+
+    /// Lets the puzzle be solved with `solve_reader` from input that's too big, or too corrupt,
+    /// to read as text.
+    pub const fn streamed(self, solve_reader: SolveReader) -> Self {
+        Puzzle {
+            solve_reader: Some(solve_reader),
+            ..self
+        }
+    }
+
+    /// Whether the puzzle can be solved from input read a chunk at a time.
+    pub fn streams(&self) -> bool {
+        self.solve_reader.is_some()
+    }
+
+    pub fn parse(&self, input: &str) -> Result<ParsedInput, ParseError> {
+        (self.parse)(input)
+    }
+
+    /// Solves this part from input parsed by this puzzle, or returns `None` if the part is
+    /// unsolved.
+    pub fn solve_parsed(&self, input: &ParsedInput) -> Option<Answer> {
+        (self.solve)(input)
+    }
+
+    /// Draws input parsed by this puzzle with the working behind this part's answer, if the
+    /// puzzle supports it.
+    pub fn render(&self, input: &ParsedInput) -> Option<Render> {
+        (self.render)(input, self.part)
+    }
+
+    /// Draws input parsed by this puzzle as an image of the working behind this part's answer, if
+    /// the puzzle supports it.
+    pub fn image(&self, input: &ParsedInput) -> Option<Image> {
+        (self.image)(input, self.part)
+    }
+
+    /// Explains this part's answer from input parsed by this puzzle, if the puzzle supports it.
+    pub fn explain(&self, input: &ParsedInput) -> Option<String> {
+        (self.explain)(input, self.part)
+    }
+
+    /// Solves both parts of the puzzle from input read a chunk at a time, or returns `None` if the
+    /// puzzle needs its whole input as text.
+    pub fn solve_reader(
+        &self,
+        reader: &mut dyn Read,
+    ) -> Option<io::Result<(Answer, Option<Answer>)>> {
+        self.solve_reader.map(|solve| solve(reader))
+    }
+
+    /// Parses the puzzle input and solves this part, or returns `None` if the part is unsolved.
+    pub fn solve(&self, input: &str) -> Result<Option<Answer>, ParseError> {
+        Ok(self.solve_parsed(&self.parse(input)?))
+    }
+}
+
+fn parse<S: Solution>(input: &str) -> Result<ParsedInput, ParseError> {
+    Ok(ParsedInput(Box::new(S::parse(input)?)))
+}
+
+fn downcast<S: Solution>(input: &ParsedInput) -> &S::Input {
+    input
+        .0
+        .downcast_ref()
+        .expect("input was parsed by a different puzzle")
+}
+
+fn solve_part1<S: Solution>(input: &ParsedInput) -> Option<Answer> {
+    Some(S::part1(downcast::<S>(input)))
+}
+
+fn solve_part2<S: Solution>(input: &ParsedInput) -> Option<Answer> {
+    S::part2(downcast::<S>(input))
+}
+
+fn render<S: Solution>(input: &ParsedInput, part: u8) -> Option<Render> {
+    S::render(downcast::<S>(input), part)
+}
+
+fn image<S: Solution>(input: &ParsedInput, part: u8) -> Option<Image> {
+    S::image(downcast::<S>(input), part)
+}
+
+fn explain<S: Solution>(input: &ParsedInput, part: u8) -> Option<String> {
+    S::explain(downcast::<S>(input), part)
+}
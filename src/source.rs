@@ -0,0 +1,152 @@
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+/// Environment variable naming a directory of `dayN.txt` puzzle inputs.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where to read puzzle inputs from.
+#[derive(Debug, PartialEq)]
+pub enum InputSource {
+    /// A single input file, used for whichever day is being solved.
+    File(PathBuf),
+    /// Standard input, used for whichever day is being solved.
+    Stdin,
+    /// A directory holding one `dayN.txt` file per day.
+    Directory(PathBuf),
+}
+
+impl InputSource {
+    /// Chooses the input source from an `--input` argument (`-` meaning stdin) if one was given,
+    /// otherwise from `AOC_INPUT_DIR`, then the per-user input directory if it exists, and
+    /// finally the inputs that ship with the crate.
+    pub fn resolve(input: Option<&str>) -> InputSource {
+        choose(input, env::var_os(INPUT_DIR_VAR).map(PathBuf::from), || {
+            user_input_dir().filter(|dir| dir.is_dir())
+        })
+    }
+
+    pub fn read(&self, day: u8) -> io::Result<String> {
+        match self {
+            InputSource::File(path) => fs::read_to_string(path),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+            InputSource::Directory(dir) => fs::read_to_string(dir.join(format!("day{}.txt", day))),
+        }
+    }
+
+    /// Opens the input for `day` to be read a chunk at a time, rather than all at once as text.
+    pub fn open(&self, day: u8) -> io::Result<Box<dyn Read + Send>> {
+        Ok(match self {
+            InputSource::File(path) => Box::new(fs::File::open(path)?),
+            InputSource::Stdin => Box::new(io::stdin()),
+            InputSource::Directory(dir) => {
+                Box::new(fs::File::open(dir.join(format!("day{}.txt", day)))?)
+            }
+        })
+    }
+
+    /// Names the file (or stream) the input for `day` is read from, for use in diagnostics.
+    pub fn name(&self, day: u8) -> String {
+        match self {
+            InputSource::File(path) => path.display().to_string(),
+            InputSource::Stdin => "<stdin>".to_string(),
+            InputSource::Directory(dir) => {
+                dir.join(format!("day{}.txt", day)).display().to_string()
+            }
+        }
+    }
+}
+
+/// Chooses the input source in order of precedence, given the directory named by
+/// `AOC_INPUT_DIR` and a way to find the per-user input directory if it exists.
+fn choose(
+    input: Option<&str>,
+    input_dir: Option<PathBuf>,
+    user_dir: impl FnOnce() -> Option<PathBuf>,
+) -> InputSource {
+    match input {
+        Some("-") => InputSource::Stdin,
+        Some(path) => InputSource::File(PathBuf::from(path)),
+        None => match input_dir.or_else(user_dir) {
+            Some(dir) => InputSource::Directory(dir),
+            None => InputSource::Directory(bundled_input_dir()),
+        },
+    }
+}
+
+/// The per-user input directory, `$XDG_DATA_HOME/advent-of-code-2024` or
+/// `~/.local/share/advent-of-code-2024`.
+fn user_input_dir() -> Option<PathBuf> {
+    env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| {
+            env::var_os("HOME")
+                .or_else(|| env::var_os("USERPROFILE"))
+                .map(|home| PathBuf::from(home).join(".local").join("share"))
+        })
+        .map(|dir| dir.join("advent-of-code-2024"))
+}
+
+/// The inputs checked in alongside the source, found regardless of the working directory.
+fn bundled_input_dir() -> PathBuf {
+    PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/src/input"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_in_order_of_precedence() {
+        let dir = |name: &str| Some(PathBuf::from(name));
+        assert_eq!(
+            choose(Some("-"), dir("env"), || dir("user")),
+            InputSource::Stdin
+        );
+        assert_eq!(
+            choose(Some("day1.txt"), dir("env"), || dir("user")),
+            InputSource::File(PathBuf::from("day1.txt"))
+        );
+        assert_eq!(
+            choose(None, dir("env"), || dir("user")),
+            InputSource::Directory(PathBuf::from("env"))
+        );
+        assert_eq!(
+            choose(None, None, || dir("user")),
+            InputSource::Directory(PathBuf::from("user"))
+        );
+        assert_eq!(
+            choose(None, None, || None),
+            InputSource::Directory(bundled_input_dir())
+        );
+    }
+
+    #[test]
+    fn read_and_name_inputs() {
+        let dir = InputSource::Directory(bundled_input_dir());
+        assert!(dir.name(3).ends_with("day3.txt"));
+        assert_eq!(
+            dir.read(3).unwrap(),
+            fs::read_to_string(dir.name(3)).unwrap()
+        );
+        let mut opened = Vec::new();
+        dir.open(3).unwrap().read_to_end(&mut opened).unwrap();
+        assert_eq!(opened, fs::read(dir.name(3)).unwrap());
+        assert_eq!(InputSource::Stdin.name(3), "<stdin>");
+
+        let file = InputSource::File(bundled_input_dir().join("day1.txt"));
+        assert_eq!(file.name(3), dir.name(1));
+        assert!(InputSource::Directory(bundled_input_dir())
+            .read(26)
+            .is_err());
+        assert!(InputSource::Directory(bundled_input_dir())
+            .open(26)
+            .is_err());
+    }
+}